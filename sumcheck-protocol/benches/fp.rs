@@ -0,0 +1,182 @@
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+use sumcheck_protocol::field::Field;
+use sumcheck_protocol::fp::Fp;
+
+/// The division based arithmetic `Fp` used before the Goldilocks specific reduction.
+mod reference {
+    use sumcheck_protocol::fp::{FiniteField, Fp};
+
+    const MODULO: u128 = Fp::MODULO as u128;
+
+    pub fn add(a: Fp, b: Fp) -> Fp {
+        Fp(((a.0 as u128 + b.0 as u128) % MODULO) as u64)
+    }
+
+    pub fn mul(a: Fp, b: Fp) -> Fp {
+        Fp(((a.0 as u128 * b.0 as u128) % MODULO) as u64)
+    }
+
+    /// Algorithm 16 in "Efficient Software-Implementation of Finite Fields with Applications to Cryptography"
+    pub fn inverse(a: Fp) -> Fp {
+        let modulo = Fp::MODULO;
+
+        if a.0 == 0 {
+            return Fp(0);
+        }
+
+        let mut v: u64 = a.0;
+        let mut u: u64 = modulo;
+        let mut s: u64 = 1;
+        let mut r: u64 = 0;
+
+        let mut carry: bool;
+        let mut borrow: bool;
+
+        while u != 1 && v != 1 {
+            while v & 1 == 0 {
+                v >>= 1;
+                if s & 1 == 0 {
+                    s >>= 1;
+                } else {
+                    (s, carry) = u64::overflowing_add(s, modulo);
+                    s >>= 1;
+                    if carry {
+                        s |= 1 << 63;
+                    }
+                }
+            }
+
+            while u & 1 == 0 {
+                u >>= 1;
+                if r & 1 == 0 {
+                    r >>= 1;
+                } else {
+                    (r, carry) = u64::overflowing_add(r, modulo);
+                    r >>= 1;
+                    if carry {
+                        r |= 1 << 63;
+                    }
+                }
+            }
+
+            if v >= u {
+                v -= u;
+                (s, borrow) = u64::overflowing_sub(s, r);
+                if borrow {
+                    s = u64::overflowing_add(s, modulo).0;
+                }
+            } else {
+                u -= v;
+                (r, borrow) = u64::overflowing_sub(r, s);
+                if borrow {
+                    r = u64::overflowing_add(r, modulo).0;
+                }
+            }
+        }
+
+        if u == 1 {
+            Fp((r as u128 % MODULO) as u64)
+        } else {
+            Fp((s as u128 % MODULO) as u64)
+        }
+    }
+
+    pub fn multi_inv(a: &[Fp]) -> Vec<Fp> {
+        let mut partials = vec![Fp(0); a.len() + 1];
+
+        partials[0] = Fp(1);
+        for i in 0..a.len() {
+            partials[i + 1] = mul(partials[i], a[i]);
+        }
+
+        let mut inv = inverse(partials[a.len()]);
+        let mut outputs = vec![Fp(0); a.len()];
+
+        for i in (0..a.len()).rev() {
+            outputs[i] = mul(partials[i], inv);
+
+            if a[i] == Fp(0) {
+                outputs[i] = Fp(1);
+            }
+
+            inv = mul(inv, a[i]);
+        }
+
+        outputs
+    }
+}
+
+const LEN: usize = 1 << 12;
+
+fn elements() -> Vec<Fp> {
+    (0..LEN).map(|_| Fp::sample()).collect()
+}
+
+/// Folds the whole vector so the loop cannot be vectorized away or skipped.
+fn fold(values: &[Fp], op: impl Fn(Fp, Fp) -> Fp) -> Fp {
+    values.iter().fold(Fp(1), |acc, &x| op(acc, x))
+}
+
+fn bench_add(c: &mut Criterion) {
+    let values = elements();
+    let mut group = c.benchmark_group("add");
+
+    group.bench_function("reference", |b| {
+        b.iter(|| fold(black_box(&values), reference::add))
+    });
+    group.bench_function("goldilocks", |b| {
+        b.iter(|| fold(black_box(&values), |x, y| x + y))
+    });
+
+    group.finish();
+}
+
+fn bench_mul(c: &mut Criterion) {
+    let values = elements();
+    let mut group = c.benchmark_group("mul");
+
+    group.bench_function("reference", |b| {
+        b.iter(|| fold(black_box(&values), reference::mul))
+    });
+    group.bench_function("goldilocks", |b| {
+        b.iter(|| fold(black_box(&values), |x, y| x * y))
+    });
+
+    group.finish();
+}
+
+fn bench_inverse(c: &mut Criterion) {
+    let mut group = c.benchmark_group("inverse");
+
+    group.bench_function("reference", |b| {
+        b.iter_batched(Fp::sample, reference::inverse, BatchSize::SmallInput)
+    });
+    group.bench_function("goldilocks", |b| {
+        b.iter_batched(Fp::sample, |x| x.inverse(), BatchSize::SmallInput)
+    });
+
+    group.finish();
+}
+
+fn bench_multi_inv(c: &mut Criterion) {
+    let values = elements();
+    let mut group = c.benchmark_group("multi_inv");
+
+    group.bench_function("reference", |b| {
+        b.iter(|| reference::multi_inv(black_box(&values)))
+    });
+    group.bench_function("goldilocks", |b| {
+        b.iter(|| Fp::multi_inv(black_box(&values)))
+    });
+
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_add,
+    bench_mul,
+    bench_inverse,
+    bench_multi_inv
+);
+criterion_main!(benches);
@@ -0,0 +1,170 @@
+use crate::field::Field;
+use rand::random;
+use std::ops::{Add, Div, Mul, Neg, Sub, SubAssign};
+
+/// BabyBear prime field, `p = 2^31 - 2^27 + 1`, elements are kept reduced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BabyBear(u32);
+
+impl BabyBear {
+    /// dec: 2013265921
+    pub const MODULO: u32 = (1 << 31) - (1 << 27) + 1;
+
+    pub fn new(value: u32) -> Self {
+        BabyBear(value % Self::MODULO)
+    }
+
+    pub fn value(&self) -> u32 {
+        self.0
+    }
+}
+
+impl Field for BabyBear {
+    const NUM_BYTES: usize = 4;
+
+    fn zero() -> Self {
+        BabyBear(0)
+    }
+
+    fn one() -> Self {
+        BabyBear(1)
+    }
+
+    fn from_u64(value: u64) -> Self {
+        BabyBear((value % Self::MODULO as u64) as u32)
+    }
+
+    /// Fermat's little theorem, `a^(p - 2)`.
+    fn inverse(&self) -> Self {
+        self.pow(Self::MODULO as u64 - 2)
+    }
+
+    fn sample() -> Self {
+        Self::from_u64(random::<u64>())
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        self.0.to_le_bytes().to_vec()
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let value = u32::from_le_bytes(bytes.try_into().ok()?);
+
+        if value >= Self::MODULO {
+            return None;
+        }
+
+        Some(BabyBear(value))
+    }
+}
+
+impl Add for BabyBear {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::from_u64(self.0 as u64 + rhs.0 as u64)
+    }
+}
+
+impl Sub for BabyBear {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::from_u64(self.0 as u64 + Self::MODULO as u64 - rhs.0 as u64)
+    }
+}
+
+impl SubAssign for BabyBear {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul for BabyBear {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self::from_u64(self.0 as u64 * rhs.0 as u64)
+    }
+}
+
+impl Div for BabyBear {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self::Output {
+        self * rhs.inverse()
+    }
+}
+
+impl Neg for BabyBear {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::zero() - self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::babybear::BabyBear;
+    use crate::field::Field;
+    use crate::mpolynomial::MPolynomial;
+    use crate::proof::{prove_non_interactive, verify_proof, SumcheckProof};
+    use crate::SumcheckProtocol;
+
+    #[test]
+    fn test_arithmetic() {
+        let a = BabyBear::new(BabyBear::MODULO - 1);
+        let b = BabyBear::new(5);
+
+        assert_eq!(a + b, BabyBear::new(4));
+        assert_eq!(b - a, BabyBear::new(6));
+        assert_eq!(a * a, BabyBear::one());
+        assert_eq!(-BabyBear::zero(), BabyBear::zero());
+        assert_eq!(b * b.inverse(), BabyBear::one());
+        assert_eq!(b / b, BabyBear::one());
+    }
+
+    #[test]
+    fn test_bytes_canonical() {
+        let a = BabyBear::new(123456);
+
+        assert_eq!(BabyBear::from_bytes(&a.to_bytes()), Some(a));
+        assert_eq!(BabyBear::from_bytes(&BabyBear::MODULO.to_le_bytes()), None);
+        assert_eq!(BabyBear::from_bytes(&[0; 3]), None);
+    }
+
+    fn polynomial() -> MPolynomial<BabyBear> {
+        MPolynomial::from_terms(
+            3,
+            vec![
+                (BabyBear::new(3), vec![2, 1, 3]),
+                (BabyBear::new(1), vec![1, 1, 1]),
+                (BabyBear::new(9), vec![1, 0, 0]),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_sumcheck_protocol() {
+        let mut protocol = SumcheckProtocol::new(polynomial());
+
+        while let Some(step) = protocol.prove() {
+            assert!(protocol.verify(Some(step)).is_ok());
+        }
+
+        assert!(protocol.is_verifier_accept());
+    }
+
+    #[test]
+    fn test_non_interactive_proof() {
+        let polynomial = polynomial();
+        let claim = polynomial.sum_over_hyper_cube(None);
+
+        let proof = prove_non_interactive(&polynomial);
+        let decoded = SumcheckProof::<BabyBear>::from_bytes(&proof.to_bytes()).unwrap();
+
+        assert!(verify_proof(&polynomial, &decoded, claim).is_ok());
+    }
+}
@@ -0,0 +1,445 @@
+use crate::error::SumcheckError;
+use crate::field::Field;
+use crate::fp::Fp;
+use crate::interpolation::InterpolationDomain;
+use crate::proof::{prove_rounds, SumcheckProof};
+use crate::prover::Prover;
+use crate::transcript::Transcript;
+use crate::verifier::{Oracle, SumcheckVerifier};
+use std::cmp::Ordering;
+
+/// `2^exponent`
+fn power_of_two<F: Field>(exponent: usize) -> F {
+    F::from_u64(2).pow(exponent as u64)
+}
+
+/// Runs several sumcheck instances as one, over `sum_i coefficients[i] * P_i`.
+///
+/// An instance over `n_i < n` variables is padded with `n - n_i` leading dummy
+/// variables. It does not depend on them, so its sum over the larger hypercube is
+/// scaled by `2^(n - n_i)` and its round polynomials in the dummy rounds are constants.
+pub struct BatchedProver<'a, F: Field = Fp> {
+    instances: Vec<Box<dyn Prover<F> + 'a>>,
+    coefficients: Vec<F>,
+    claims: Vec<F>,
+    number_of_vars: usize,
+    degree: usize,
+    domain: InterpolationDomain<F>,
+    round: usize,
+}
+
+impl<'a, F: Field> BatchedProver<'a, F> {
+    /// `coefficients` are sampled by the verifier after it learned every claim.
+    pub fn new(instances: Vec<Box<dyn Prover<F> + 'a>>, coefficients: Vec<F>) -> Self {
+        assert!(!instances.is_empty(), "batch needs at least one instance");
+        assert_eq!(instances.len(), coefficients.len());
+
+        let claims = instances.iter().map(|p| p.claimed_sum()).collect();
+        let number_of_vars = instances.iter().map(|p| p.number_of_vars()).max().unwrap();
+        let degree = instances.iter().map(|p| p.degree()).max().unwrap().max(1);
+
+        Self {
+            instances,
+            coefficients,
+            claims,
+            number_of_vars,
+            degree,
+            domain: InterpolationDomain::new(degree),
+            round: 0,
+        }
+    }
+
+    /// The sums of the single instances, the verifier needs them to combine the claim.
+    pub fn claims(&self) -> &[F] {
+        &self.claims
+    }
+}
+
+impl<F: Field> Prover<F> for BatchedProver<'_, F> {
+    fn claimed_sum(&self) -> F {
+        self.instances
+            .iter()
+            .zip(&self.claims)
+            .zip(&self.coefficients)
+            .fold(F::zero(), |acc, ((instance, claim), coefficient)| {
+                let padding = self.number_of_vars - instance.number_of_vars();
+                acc + *coefficient * power_of_two::<F>(padding) * *claim
+            })
+    }
+
+    fn number_of_vars(&self) -> usize {
+        self.number_of_vars
+    }
+
+    fn degree(&self) -> usize {
+        self.degree
+    }
+
+    fn round_evaluations(&mut self, challenge: Option<F>) -> Option<Vec<F>> {
+        let round = self.round;
+        if round > self.number_of_vars {
+            return None;
+        }
+
+        self.round += 1;
+
+        let mut combined = vec![F::zero(); self.degree + 1];
+
+        for ((instance, claim), coefficient) in self
+            .instances
+            .iter_mut()
+            .zip(&self.claims)
+            .zip(&self.coefficients)
+        {
+            let padding = self.number_of_vars - instance.number_of_vars();
+
+            let evaluations = match round.cmp(&padding) {
+                Ordering::Less => {
+                    vec![power_of_two::<F>(padding - round - 1) * *claim]
+                }
+                // The challenge of the last dummy round is not one of the instance's
+                Ordering::Equal => match instance.round_evaluations(None) {
+                    Some(evaluations) => evaluations,
+                    None => continue,
+                },
+                Ordering::Greater => match instance.round_evaluations(challenge) {
+                    Some(evaluations) => evaluations,
+                    None => continue,
+                },
+            };
+
+            for (t, value) in combined.iter_mut().enumerate() {
+                let evaluation = match evaluations.get(t) {
+                    Some(evaluation) => *evaluation,
+                    None => self.domain.evaluate(&evaluations, F::from_u64(t as u64)),
+                };
+
+                *value = *value + *coefficient * evaluation;
+            }
+        }
+
+        if round == self.number_of_vars {
+            return None;
+        }
+
+        Some(combined)
+    }
+
+    fn final_evaluation(&self) -> F {
+        self.instances
+            .iter()
+            .zip(&self.coefficients)
+            .fold(F::zero(), |acc, (instance, coefficient)| {
+                acc + *coefficient * instance.final_evaluation()
+            })
+    }
+}
+
+/// What the verifier knows about one instance of a batch.
+pub struct BatchedClaim<'a, F: Field = Fp> {
+    pub claim: F,
+    pub number_of_vars: usize,
+    pub degree: usize,
+    pub oracle: Oracle<'a, F>,
+}
+
+impl<'a, F: Field> BatchedClaim<'a, F> {
+    pub fn new(
+        claim: F,
+        number_of_vars: usize,
+        degree: usize,
+        oracle: impl Fn(&[F]) -> F + 'a,
+    ) -> Self {
+        Self {
+            claim,
+            number_of_vars,
+            degree,
+            oracle: Box::new(oracle),
+        }
+    }
+}
+
+/// Verifier for the combined claim `sum_i coefficients[i] * 2^(n - n_i) * claims[i]`.
+///
+/// The oracle of instance `i` is queried at the last `n_i` coordinates of the
+/// random point, the leading ones belong to its dummy variables.
+pub fn batched_verifier<'a, F: Field>(
+    claims: Vec<BatchedClaim<'a, F>>,
+    coefficients: &[F],
+) -> SumcheckVerifier<'a, F> {
+    assert!(!claims.is_empty(), "batch needs at least one instance");
+    assert_eq!(claims.len(), coefficients.len());
+
+    let number_of_vars = claims.iter().map(|c| c.number_of_vars).max().unwrap();
+    let degree = claims.iter().map(|c| c.degree).max().unwrap();
+
+    let claim = claims
+        .iter()
+        .zip(coefficients)
+        .fold(F::zero(), |acc, (c, coefficient)| {
+            acc + *coefficient * power_of_two::<F>(number_of_vars - c.number_of_vars) * c.claim
+        });
+
+    let coefficients = coefficients.to_vec();
+
+    SumcheckVerifier::new(claim, number_of_vars, degree, move |x| {
+        claims
+            .iter()
+            .zip(&coefficients)
+            .fold(F::zero(), |acc, (c, coefficient)| {
+                acc + *coefficient * (c.oracle)(&x[number_of_vars - c.number_of_vars..])
+            })
+    })
+}
+
+/// One instance as seen by the transcript, `statement` identifies its polynomial like
+/// in `proof::new_transcript`.
+struct Instance<'a, F> {
+    statement: &'a [u8],
+    number_of_vars: usize,
+    degree: usize,
+    claim: F,
+}
+
+/// Binds every instance, its size, degree and claim, then draws the combination coefficients.
+fn new_transcript<F: Field>(instances: &[Instance<'_, F>]) -> (Transcript, Vec<F>) {
+    let mut transcript = Transcript::new(b"batched_sumcheck");
+    transcript.absorb_u64(b"number_of_instances", instances.len() as u64);
+
+    for instance in instances {
+        transcript.absorb_bytes(b"statement", instance.statement);
+        transcript.absorb_u64(b"number_of_vars", instance.number_of_vars as u64);
+        transcript.absorb_u64(b"degree", instance.degree as u64);
+        transcript.absorb_field(b"claim", &instance.claim);
+    }
+
+    let coefficients = instances
+        .iter()
+        .map(|_| transcript.challenge(b"coefficient"))
+        .collect();
+
+    (transcript, coefficients)
+}
+
+/// Proves all instances with a single non-interactive sumcheck, `statements[i]`
+/// identifies the polynomial of `instances[i]`, e.g. its encoding or a commitment.
+pub fn prove_batch<'a, F: Field>(
+    instances: Vec<Box<dyn Prover<F> + 'a>>,
+    statements: &[&[u8]],
+) -> SumcheckProof<F> {
+    assert_eq!(instances.len(), statements.len());
+
+    let bound: Vec<Instance<F>> = instances
+        .iter()
+        .zip(statements)
+        .map(|(p, statement)| Instance {
+            statement,
+            number_of_vars: p.number_of_vars(),
+            degree: p.degree(),
+            claim: p.claimed_sum(),
+        })
+        .collect();
+    let (mut transcript, coefficients) = new_transcript(&bound);
+
+    let mut prover = BatchedProver::new(instances, coefficients);
+    let claimed_sum = prover.claimed_sum();
+    let (round_messages, _) = prove_rounds(&mut prover, &mut transcript);
+
+    SumcheckProof {
+        claimed_sum,
+        round_messages,
+        final_evaluation: prover.final_evaluation(),
+    }
+}
+
+/// Checks a proof from `prove_batch` against the claim of every instance, with the
+/// statements the proof was produced for.
+pub fn verify_batch<F: Field>(
+    claims: Vec<BatchedClaim<'_, F>>,
+    statements: &[&[u8]],
+    proof: &SumcheckProof<F>,
+) -> Result<Vec<F>, SumcheckError> {
+    assert_eq!(claims.len(), statements.len());
+
+    let bound: Vec<Instance<F>> = claims
+        .iter()
+        .zip(statements)
+        .map(|(c, statement)| Instance {
+            statement,
+            number_of_vars: c.number_of_vars,
+            degree: c.degree,
+            claim: c.claim,
+        })
+        .collect();
+    let (mut transcript, coefficients) = new_transcript(&bound);
+
+    batched_verifier(claims, &coefficients).verify_proof_with_transcript(proof, &mut transcript)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::batch::{batched_verifier, prove_batch, verify_batch, BatchedClaim, BatchedProver};
+    use crate::error::SumcheckError;
+    use crate::fp::Fp;
+    use crate::mle::DenseMultilinearExtension;
+    use crate::mpolynomial::MPolynomial;
+    use crate::pcs::{HashCommitment, PolynomialCommitmentScheme};
+    use crate::prover::{MultilinearProver, ProductSumcheck, Prover, SumcheckProver};
+    use crate::VerifierState;
+
+    fn polynomials() -> Vec<MPolynomial> {
+        vec![
+            MPolynomial::from(vec![Fp(2), Fp(3), Fp(5), Fp(7)], vec![1, 1, 1]),
+            MPolynomial::from(vec![Fp(1), Fp(4), Fp(9)], vec![3, 2, 1]),
+            MPolynomial::from(vec![Fp(8), Fp(6), Fp(4), Fp(2)], vec![2, 2, 2]),
+        ]
+    }
+
+    fn table(number_of_vars: usize, seed: u64) -> DenseMultilinearExtension {
+        DenseMultilinearExtension::from(
+            (0..1u64 << number_of_vars)
+                .map(|i| Fp(i * seed + 3))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_same_number_of_vars() {
+        let polynomials = polynomials();
+
+        let encodings: Vec<Vec<u8>> = polynomials.iter().map(|p| p.to_bytes()).collect();
+        let statements: Vec<&[u8]> = encodings.iter().map(Vec::as_slice).collect();
+
+        let provers = polynomials
+            .iter()
+            .map(|p| Box::new(SumcheckProver::new(p.clone())) as Box<dyn Prover>)
+            .collect();
+        let proof = prove_batch(provers, &statements);
+
+        let claims = || {
+            polynomials
+                .iter()
+                .map(|p| {
+                    BatchedClaim::new(p.sum_over_hyper_cube(None), 3, p.degree_ind(), |x| {
+                        p.eval(x)
+                    })
+                })
+                .collect()
+        };
+
+        assert_eq!(proof.round_messages.len(), 3);
+        assert!(verify_batch(claims(), &statements, &proof).is_ok());
+
+        let mut wrong: Vec<BatchedClaim> = claims();
+        wrong[1].claim = wrong[1].claim + Fp(1);
+        assert_eq!(
+            verify_batch(wrong, &statements, &proof),
+            Err(SumcheckError::ClaimMismatch)
+        );
+    }
+
+    #[test]
+    fn test_differing_number_of_vars() {
+        let tables = [table(2, 3), table(4, 5), table(5, 7), table(3, 11)];
+
+        let provers: Vec<Box<dyn Prover>> = vec![
+            Box::new(MultilinearProver::new(tables[0].clone())),
+            Box::new(MultilinearProver::new(tables[1].clone())),
+            Box::new(MultilinearProver::new(tables[2].clone())),
+            Box::new(ProductSumcheck::new(vec![
+                tables[3].clone(),
+                tables[3].clone(),
+            ])),
+        ];
+        let commitments: Vec<[u8; 32]> =
+            tables.iter().map(|t| HashCommitment.commit(t).0).collect();
+        let statements: Vec<&[u8]> = commitments.iter().map(|c| c.as_slice()).collect();
+        let proof = prove_batch(provers, &statements);
+
+        let square_sum = tables[3]
+            .evaluations
+            .iter()
+            .fold(Fp(0), |acc, x| acc + *x * *x);
+        let claims = vec![
+            BatchedClaim::new(tables[0].sum_over_hyper_cube(), 2, 1, |x| tables[0].eval(x)),
+            BatchedClaim::new(tables[1].sum_over_hyper_cube(), 4, 1, |x| tables[1].eval(x)),
+            BatchedClaim::new(tables[2].sum_over_hyper_cube(), 5, 1, |x| tables[2].eval(x)),
+            BatchedClaim::new(square_sum, 3, 2, |x| tables[3].eval(x) * tables[3].eval(x)),
+        ];
+
+        assert_eq!(proof.round_messages.len(), 5);
+        assert!(verify_batch(claims, &statements, &proof).is_ok());
+    }
+
+    #[test]
+    fn test_instances_are_bound() {
+        // Same sizes, degrees and claims, only the first table is permuted
+        let a = [table(3, 5), table(3, 7)];
+        let permuted = a[0].evaluations.iter().rev().copied().collect();
+        let b = [DenseMultilinearExtension::from(permuted), a[1].clone()];
+        assert_eq!(a[0].sum_over_hyper_cube(), b[0].sum_over_hyper_cube());
+
+        let a_commitments: Vec<[u8; 32]> = a.iter().map(|t| HashCommitment.commit(t).0).collect();
+        let b_commitments: Vec<[u8; 32]> = b.iter().map(|t| HashCommitment.commit(t).0).collect();
+        let a_statements: Vec<&[u8]> = a_commitments.iter().map(|c| c.as_slice()).collect();
+        let b_statements: Vec<&[u8]> = b_commitments.iter().map(|c| c.as_slice()).collect();
+
+        let provers = a
+            .iter()
+            .map(|t| Box::new(MultilinearProver::new(t.clone())) as Box<dyn Prover>)
+            .collect();
+        let proof = prove_batch(provers, &a_statements);
+
+        let a_claims = a
+            .iter()
+            .map(|t| BatchedClaim::new(t.sum_over_hyper_cube(), 3, 1, |x| t.eval(x)))
+            .collect();
+        let b_claims = b
+            .iter()
+            .map(|t| BatchedClaim::new(t.sum_over_hyper_cube(), 3, 1, |x| t.eval(x)))
+            .collect();
+
+        assert!(verify_batch(a_claims, &a_statements, &proof).is_ok());
+        assert!(verify_batch(b_claims, &b_statements, &proof).is_err());
+    }
+
+    #[test]
+    fn test_interactive() {
+        let small = table(2, 3);
+        let large = table(4, 5);
+        let coefficients = [Fp(17), Fp(19)];
+
+        let mut prover = BatchedProver::new(
+            vec![
+                Box::new(MultilinearProver::new(small.clone())),
+                Box::new(MultilinearProver::new(large.clone())),
+            ],
+            coefficients.to_vec(),
+        );
+        let mut verifier = batched_verifier(
+            vec![
+                BatchedClaim::new(prover.claims()[0], 2, 1, |x| small.eval(x)),
+                BatchedClaim::new(prover.claims()[1], 4, 1, |x| large.eval(x)),
+            ],
+            &coefficients,
+        );
+
+        let mut challenge = None;
+        loop {
+            match verifier.receive(prover.round(challenge)) {
+                Ok(VerifierState::Challenge(next)) => challenge = Some(next),
+                Ok(VerifierState::Accepted) => break,
+                Err(err) => panic!("honest batch rejected: {}", err),
+            }
+        }
+
+        // The small instance only saw the last two challenges
+        let randomness = verifier.randomness().to_vec();
+        assert!(prover.round(randomness.last().copied()).is_none());
+        assert_eq!(
+            prover.final_evaluation(),
+            coefficients[0] * small.eval(&randomness[2..])
+                + coefficients[1] * large.eval(&randomness)
+        );
+    }
+}
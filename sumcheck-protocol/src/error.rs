@@ -0,0 +1,188 @@
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SumcheckError {
+    /// The prover did not send a round polynomial.
+    MissingMessage,
+    /// A message was received after the verifier already reached a decision.
+    ProtocolFinished,
+    /// The proof was produced for a different sum than the one being checked.
+    ClaimMismatch,
+    /// The proof does not contain one round polynomial per variable.
+    WrongNumberOfRounds { expected: usize, received: usize },
+    /// A round polynomial exceeds the degree of the polynomial being summed.
+    DegreeTooHigh {
+        round: usize,
+        degree: usize,
+        max_degree: usize,
+    },
+    /// `g_i(0) + g_i(1)` does not match the value carried over from the previous round.
+    RoundSumMismatch { round: usize },
+    /// The last round polynomial disagrees with the evaluation of the polynomial itself.
+    FinalEvaluationMismatch,
+    /// The opening proof for the final evaluation does not match the commitment.
+    InvalidOpening,
+}
+
+impl Display for SumcheckError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SumcheckError::MissingMessage => write!(f, "prover did not send a round polynomial"),
+            SumcheckError::ProtocolFinished => write!(f, "protocol is already finished"),
+            SumcheckError::ClaimMismatch => write!(f, "proof is for a different claimed sum"),
+            SumcheckError::WrongNumberOfRounds { expected, received } => {
+                write!(f, "expected {} rounds, received {}", expected, received)
+            }
+            SumcheckError::DegreeTooHigh {
+                round,
+                degree,
+                max_degree,
+            } => write!(
+                f,
+                "round {} polynomial has degree {}, at most {} is allowed",
+                round, degree, max_degree
+            ),
+            SumcheckError::RoundSumMismatch { round } => {
+                write!(
+                    f,
+                    "round {} polynomial does not sum to the expected value",
+                    round
+                )
+            }
+            SumcheckError::FinalEvaluationMismatch => {
+                write!(f, "final evaluation does not match the polynomial")
+            }
+            SumcheckError::InvalidOpening => {
+                write!(f, "opening proof does not match the commitment")
+            }
+        }
+    }
+}
+
+impl std::error::Error for SumcheckError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DecodingError {
+    /// The encoding was produced by an unknown version of the format.
+    UnsupportedVersion(u8),
+    /// The input ended before the proof was fully read.
+    UnexpectedEnd,
+    /// A field element is not in its canonical encoding, e.g. an `Fp` not below `Fp::MODULO`.
+    NonCanonicalElement,
+    /// Bytes are left over after the proof was read.
+    TrailingBytes,
+}
+
+impl Display for DecodingError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DecodingError::UnsupportedVersion(version) => {
+                write!(f, "unsupported encoding version {}", version)
+            }
+            DecodingError::UnexpectedEnd => write!(f, "unexpected end of input"),
+            DecodingError::NonCanonicalElement => write!(f, "non-canonical field element"),
+            DecodingError::TrailingBytes => write!(f, "trailing bytes after the proof"),
+        }
+    }
+}
+
+impl std::error::Error for DecodingError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GkrError {
+    /// The proof does not contain one sumcheck per circuit layer.
+    WrongNumberOfLayers { expected: usize, received: usize },
+    /// The proof claims a different number of outputs than the circuit has.
+    WrongNumberOfOutputs { expected: usize, received: usize },
+    /// The inputs given to the verifier do not match the input width of the circuit.
+    WrongNumberOfInputs { expected: usize, received: usize },
+    /// The sumcheck reducing the claim on `layer` to its inputs failed.
+    Sumcheck { layer: usize, error: SumcheckError },
+    /// The claims left after the last layer disagree with the actual inputs.
+    InputMismatch,
+}
+
+impl Display for GkrError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GkrError::WrongNumberOfLayers { expected, received } => {
+                write!(f, "expected {} layers, received {}", expected, received)
+            }
+            GkrError::WrongNumberOfOutputs { expected, received } => {
+                write!(f, "expected {} outputs, received {}", expected, received)
+            }
+            GkrError::WrongNumberOfInputs { expected, received } => {
+                write!(f, "expected {} inputs, received {}", expected, received)
+            }
+            GkrError::Sumcheck { layer, error } => {
+                write!(f, "sumcheck of layer {} failed: {}", layer, error)
+            }
+            GkrError::InputMismatch => write!(f, "final claims do not match the inputs"),
+        }
+    }
+}
+
+impl std::error::Error for GkrError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FriError {
+    /// The proof does not commit to one layer per folding round and the last layer.
+    WrongNumberOfLayers { expected: usize, received: usize },
+    /// The proof does not answer every query.
+    WrongNumberOfQueries { expected: usize, received: usize },
+    /// An opened pair of evaluations is not in the committed layer.
+    InvalidPath { query: usize, layer: usize },
+    /// Folding the opened pair does not give the value opened in the next layer.
+    FoldingMismatch { query: usize, layer: usize },
+    /// The pair opened in the last layer is not the final value.
+    FinalValueMismatch { query: usize },
+}
+
+impl Display for FriError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FriError::WrongNumberOfLayers { expected, received } => {
+                write!(f, "expected {} layers, received {}", expected, received)
+            }
+            FriError::WrongNumberOfQueries { expected, received } => {
+                write!(f, "expected {} queries, received {}", expected, received)
+            }
+            FriError::InvalidPath { query, layer } => {
+                write!(
+                    f,
+                    "query {} does not match the commitment of layer {}",
+                    query, layer
+                )
+            }
+            FriError::FoldingMismatch { query, layer } => {
+                write!(f, "query {} is not consistent after layer {}", query, layer)
+            }
+            FriError::FinalValueMismatch { query } => {
+                write!(f, "query {} does not open the final value", query)
+            }
+        }
+    }
+}
+
+impl std::error::Error for FriError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InterpolationError {
+    /// No points were given, there is no polynomial to pick.
+    EmptyInput,
+    /// Points `first` and `second` share the same x-coordinate.
+    DuplicateNode { first: usize, second: usize },
+}
+
+impl Display for InterpolationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InterpolationError::EmptyInput => write!(f, "no points to interpolate"),
+            InterpolationError::DuplicateNode { first, second } => {
+                write!(f, "points {} and {} have the same x", first, second)
+            }
+        }
+    }
+}
+
+impl std::error::Error for InterpolationError {}
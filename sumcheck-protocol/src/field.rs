@@ -0,0 +1,118 @@
+use std::fmt::Debug;
+use std::ops::{Add, Div, Mul, Neg, Sub, SubAssign};
+
+/// Arithmetic the polynomials and the sumcheck need from a field.
+///
+/// `Fp` (Goldilocks) is the default everywhere, any other field only has to implement this trait.
+pub trait Field:
+    'static
+    + Copy
+    + Debug
+    + PartialEq
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + SubAssign
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+{
+    /// Size of the canonical encoding returned by `to_bytes`.
+    const NUM_BYTES: usize;
+
+    fn zero() -> Self;
+
+    fn one() -> Self;
+
+    fn from_u64(value: u64) -> Self;
+
+    /// Multiplicative inverse, zero is mapped to zero.
+    fn inverse(&self) -> Self;
+
+    /// Uniformly random element.
+    fn sample() -> Self;
+
+    /// Canonical little-endian encoding of `NUM_BYTES` bytes.
+    fn to_bytes(&self) -> Vec<u8>;
+
+    /// Inverse of `to_bytes`, returns `None` for a wrong length or a non-canonical encoding.
+    fn from_bytes(bytes: &[u8]) -> Option<Self>;
+
+    fn pow(&self, exponent: u64) -> Self {
+        let mut result = Self::one();
+        let mut base = *self;
+        let mut p = exponent;
+
+        while p > 0 {
+            if p & 1 == 1 {
+                result = result * base;
+            }
+
+            base = base * base;
+            p >>= 1;
+        }
+
+        result
+    }
+
+    /// Batch inversion with a single `inverse`, zeros are mapped to one.
+    fn multi_inv(a: &[Self]) -> Vec<Self> {
+        let mut partials = vec![Self::zero(); a.len() + 1];
+
+        partials[0] = Self::one();
+        for i in 0..a.len() {
+            partials[i + 1] = partials[i] * a[i];
+        }
+
+        let mut inv = partials[a.len()].inverse();
+        let mut outputs = vec![Self::zero(); a.len()];
+
+        for i in (0..a.len()).rev() {
+            outputs[i] = partials[i] * inv;
+
+            if a[i] == Self::zero() {
+                outputs[i] = Self::one();
+            }
+
+            inv = inv * a[i];
+        }
+
+        outputs
+    }
+
+    /// Product of two polynomials given by their coefficients, lowest degree first.
+    ///
+    /// Schoolbook by default, fields with an FFT-friendly subgroup can do better.
+    fn convolve(a: &[Self], b: &[Self]) -> Vec<Self> {
+        schoolbook_convolve(a, b)
+    }
+}
+
+/// `O(n * m)` product of two coefficient vectors, lowest degree first.
+pub fn schoolbook_convolve<F: Field>(a: &[F], b: &[F]) -> Vec<F> {
+    if a.is_empty() || b.is_empty() {
+        return vec![];
+    }
+
+    let mut product = vec![F::zero(); a.len() + b.len() - 1];
+
+    for (i, a_i) in a.iter().enumerate() {
+        for (j, b_j) in b.iter().enumerate() {
+            product[i + j] = product[i + j] + *a_i * *b_j;
+        }
+    }
+
+    product
+}
+
+/// Field of degree `DEGREE` over the base field `B`.
+///
+/// Lets a polynomial keep its coefficients in `B` while challenges and round
+/// messages live in the larger field.
+pub trait ExtensionField<B: Field>: Field + Mul<B, Output = Self> {
+    const DEGREE: usize;
+
+    fn from_base(value: B) -> Self;
+
+    /// The Frobenius map `x -> x^|B|`, it generates the Galois group over `B`.
+    fn frobenius(&self) -> Self;
+}
@@ -1,3 +1,5 @@
+use crate::field::{schoolbook_convolve, ExtensionField, Field};
+use crate::ntt;
 use rand::random;
 use std::cmp::PartialEq;
 use std::ops::{Add, Div, Mul, Neg, Rem, Shr, ShrAssign, Sub, SubAssign};
@@ -7,38 +9,78 @@ pub trait FiniteField {
     const MODULO: u64 = (2u128.pow(64) - 2u128.pow(32) + 1) as u64;
 }
 
-/// Could be optimized using Montgomery multiplication
-/// See section 2.3.2 of Tolga Acar's thesis
-/// https://www.microsoft.com/en-us/research/wp-content/uploads/1998/06/97Acar.pdf
+/// Size of the smaller factor from which `convolve` goes through the NTT.
+const NTT_THRESHOLD: usize = 64;
+
+/// `2^64 - MODULO`, so `2^64 = EPSILON (mod p)` and `2^96 = -1 (mod p)`.
+const EPSILON: u64 = (1 << 32) - 1;
+
+/// Element of the Goldilocks field.
+///
+/// The inner value is allowed to be any u64, every operation reduces its
+/// output below `MODULO`. Reductions use the shape of the prime instead of
+/// a division, the same way as the `reduce128` routine of Plonky2.
 #[derive(Debug, Clone, Copy)]
 pub struct Fp(pub u64);
 
 impl Fp {
     pub fn neg(&self) -> Self {
-        Fp(Self::MODULO - self.0)
+        -*self
     }
 
-    pub fn pow(&self, p0: u32) -> Self {
-        let mut result = Fp(1);
-        let mut base = *self;
-        let mut p = p0;
-
-        while p > 0 {
-            if p & 1 == 1 {
-                result = result * base;
-            }
+    /// Maps any u64 to its representative below `MODULO`.
+    #[inline]
+    fn canonical(value: u64) -> u64 {
+        if value >= Self::MODULO {
+            value - Self::MODULO
+        } else {
+            value
+        }
+    }
 
-            base = base * base;
-            p >>= 1;
+    /// Reduces a 128-bit product, `x = x_lo + 2^64 * x_hi_lo + 2^96 * x_hi_hi`.
+    #[inline]
+    fn reduce128(x: u128) -> u64 {
+        let x_lo = x as u64;
+        let x_hi = (x >> 64) as u64;
+        let x_hi_hi = x_hi >> 32;
+        let x_hi_lo = x_hi & EPSILON;
+
+        // x_lo - x_hi_hi, on borrow 2^64 was added so take EPSILON back off
+        let (mut t0, borrow) = x_lo.overflowing_sub(x_hi_hi);
+        if borrow {
+            t0 -= EPSILON;
         }
 
-        result
+        // At most (2^32 - 1)^2, fits a u64
+        let t1 = x_hi_lo * EPSILON;
+
+        let (t2, carry) = t0.overflowing_add(t1);
+        let t2 = if carry { t2 + EPSILON } else { t2 };
+
+        Self::canonical(t2)
     }
 }
 
 impl Fp {
     pub const MAX: Self = Fp(Fp::MODULO - 1);
 
+    /// Generator of the whole multiplicative group.
+    pub const GENERATOR: Self = Fp(7);
+
+    /// `MODULO - 1 = 2^32 * (2^32 - 1)`, so there are subgroups of every order up to `2^32`.
+    pub const TWO_ADICITY: usize = 32;
+
+    /// Primitive `2^TWO_ADICITY`-th root of unity, `GENERATOR^((MODULO - 1) / 2^32)`.
+    pub const TWO_ADIC_ROOT_OF_UNITY: Self = Fp(1753635133440165772);
+
+    /// Generator of the subgroup of order `2^log_size`.
+    pub fn two_adic_root_of_unity(log_size: usize) -> Self {
+        assert!(log_size <= Self::TWO_ADICITY, "no subgroup of that order");
+
+        Self::TWO_ADIC_ROOT_OF_UNITY.exp_power_of_2(Self::TWO_ADICITY - log_size)
+    }
+
     pub fn zero() -> Self {
         Fp(0)
     }
@@ -55,112 +97,137 @@ impl Fp {
         Fp((value.into() % Self::MODULO as u128) as u64)
     }
 
-    /// Algorithm 16 in "Efficient Software-Implementation of Finite Fields with Applications to Cryptography"
-    pub fn inverse(&self) -> Self {
-        let modulo = Self::MODULO;
+    /// Canonical encoding: the reduced value as little-endian u64.
+    pub fn to_bytes(&self) -> [u8; 8] {
+        Fp::from(self.0).0.to_le_bytes()
+    }
+
+    /// Inverse of `to_bytes`, values `>= MODULO` are not canonical and are rejected.
+    pub fn from_bytes(bytes: [u8; 8]) -> Option<Self> {
+        let value = u64::from_le_bytes(bytes);
 
-        if self.0 == 0 {
-            return Fp(0);
+        if value >= Self::MODULO {
+            return None;
         }
 
-        let mut v: u64 = self.0;
-        let mut u: u64 = Self::MODULO;
-        let mut s: u64 = 1;
-        let mut r: u64 = 0;
-
-        let mut carry: bool;
-        let mut borrow: bool;
-
-        while u != 1 && v != 1 {
-            while v & 1 == 0 {
-                v >>= 1;
-                if s & 1 == 0 {
-                    s >>= 1;
-                } else {
-                    (s, carry) = u64::overflowing_add(s, modulo);
-                    s >>= 1;
-                    if carry {
-                        s |= 1 << 63;
-                    }
-                }
-            }
+        Some(Fp(value))
+    }
 
-            while u & 1 == 0 {
-                u >>= 1;
-                if r & 1 == 0 {
-                    r >>= 1;
-                } else {
-                    (r, carry) = u64::overflowing_add(r, modulo);
-                    r >>= 1;
-                    if carry {
-                        r |= 1 << 63;
-                    }
-                }
-            }
+    /// `x^(p - 2)` by Fermat's little theorem, zero is mapped to zero.
+    ///
+    /// `p - 2 = 0xFFFFFFFE_FFFFFFFF` is reached with an addition chain of 64
+    /// squarings and 9 multiplications, `t_k` below stands for `x^(2^k - 1)`.
+    pub fn inverse(&self) -> Self {
+        let x = *self;
+
+        let t2 = x.square() * x;
+        let t3 = t2.square() * x;
+        let t6 = t3.exp_power_of_2(3) * t3;
+        let t12 = t6.exp_power_of_2(6) * t6;
+        let t24 = t12.exp_power_of_2(12) * t12;
+        let t30 = t24.exp_power_of_2(6) * t6;
+        let t31 = t30.square() * x;
+        let t32 = t31.square() * x;
+
+        t31.exp_power_of_2(33) * t32
+    }
 
-            if v >= u {
-                v -= u;
-                (s, borrow) = u64::overflowing_sub(s, r);
-                if borrow {
-                    s = u64::overflowing_add(s, modulo).0;
-                }
-            } else {
-                u -= v;
-                (r, borrow) = u64::overflowing_sub(r, s);
-                if borrow {
-                    r = u64::overflowing_add(r, modulo).0;
-                }
-            }
-        }
+    fn square(self) -> Self {
+        self * self
+    }
 
-        if u == 1 {
-            Fp::from(r)
-        } else {
-            Fp::from(s)
-        }
+    /// `self^(2^k)`
+    fn exp_power_of_2(self, k: usize) -> Self {
+        (0..k).fold(self, |acc, _| acc.square())
     }
+}
 
-    pub fn multi_inv(a: &Vec<Fp>) -> Vec<Fp> {
-        let mut partials = vec![Fp::zero(); a.len() + 1];
+impl FiniteField for Fp {}
+impl FiniteField for &Fp {}
 
-        partials[0] = Fp::one();
-        for i in 0..a.len() {
-            partials[i + 1] = partials[i] * a[i];
-        }
+impl Field for Fp {
+    const NUM_BYTES: usize = 8;
 
-        let mut inv = Fp::inverse(&partials[a.len()]);
-        let mut outputs = vec![Fp::zero(); a.len()];
+    fn zero() -> Self {
+        Fp::zero()
+    }
 
-        for i in (0..a.len()).rev() {
-            outputs[i] = partials[i] * inv;
+    fn one() -> Self {
+        Fp::one()
+    }
 
-            if a[i] == Fp::zero() {
-                outputs[i] = Fp::one();
-            }
+    fn from_u64(value: u64) -> Self {
+        Fp::from(value)
+    }
 
-            inv = inv * a[i];
-        }
+    fn inverse(&self) -> Self {
+        Fp::inverse(self)
+    }
+
+    fn sample() -> Self {
+        Fp::sample()
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        Fp::to_bytes(self).to_vec()
+    }
 
-        outputs
+    fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        Fp::from_bytes(bytes.try_into().ok()?)
+    }
+
+    fn convolve(a: &[Self], b: &[Self]) -> Vec<Self> {
+        if a.len().min(b.len()) < NTT_THRESHOLD {
+            schoolbook_convolve(a, b)
+        } else {
+            ntt::multiply(a, b)
+        }
     }
 }
 
-impl FiniteField for Fp {}
-impl FiniteField for &Fp {}
+/// Every field is a degree one extension of itself.
+impl ExtensionField<Fp> for Fp {
+    const DEGREE: usize = 1;
+
+    fn from_base(value: Fp) -> Self {
+        value
+    }
+
+    fn frobenius(&self) -> Self {
+        *self
+    }
+}
 
 impl Add for Fp {
     type Output = Self;
 
+    #[inline]
     fn add(self, rhs: Self) -> Self::Output {
-        Fp(((self.0 as u128 + rhs.0 as u128) % Self::MODULO as u128) as u64)
+        // An overflow drops 2^64, which is EPSILON mod p. Only non-canonical
+        // inputs can overflow a second time.
+        let (sum, carry) = self.0.overflowing_add(rhs.0);
+        let (mut sum, carry) = sum.overflowing_add(EPSILON * carry as u64);
+        if carry {
+            sum += EPSILON;
+        }
+
+        Fp(Self::canonical(sum))
     }
 }
 
 impl Sub for Fp {
     type Output = Self;
 
+    #[inline]
     fn sub(self, rhs: Self) -> Self::Output {
-        Fp(((self.0 as u128 + Self::MODULO as u128 - rhs.0 as u128) % Self::MODULO as u128) as u64)
+        // A borrow adds 2^64, which is EPSILON too much mod p
+        let (diff, borrow) = self.0.overflowing_sub(rhs.0);
+        let (mut diff, borrow) = diff.overflowing_sub(EPSILON * borrow as u64);
+        if borrow {
+            diff -= EPSILON;
+        }
+
+        Fp(Self::canonical(diff))
     }
 }
 
@@ -173,14 +240,16 @@ impl SubAssign for Fp {
 impl Mul for Fp {
     type Output = Self;
 
+    #[inline]
     fn mul(self, rhs: Self) -> Self::Output {
-        Fp(((self.0 as u128 * rhs.0 as u128) % Self::MODULO as u128) as u64)
+        Fp(Self::reduce128(self.0 as u128 * rhs.0 as u128))
     }
 }
 
 impl Div for Fp {
     type Output = Self;
 
+    #[allow(clippy::suspicious_arithmetic_impl)]
     fn div(self, rhs: Self) -> Self::Output {
         // Sanitize the input
         let a = Fp::from(self.0);
@@ -216,7 +285,10 @@ impl Neg for Fp {
     type Output = Self;
 
     fn neg(self) -> Self::Output {
-        Fp(Self::MODULO - self.0)
+        match Self::canonical(self.0) {
+            0 => Fp(0),
+            value => Fp(Self::MODULO - value),
+        }
     }
 }
 
@@ -224,10 +296,6 @@ impl PartialEq for Fp {
     fn eq(&self, other: &Self) -> bool {
         self.0 == other.0
     }
-
-    fn ne(&self, other: &Self) -> bool {
-        self.0 != other.0
-    }
 }
 
 impl PartialEq<Fp> for &Fp {
@@ -244,6 +312,7 @@ impl PartialOrd for Fp {
 
 #[cfg(test)]
 mod tests {
+    use crate::field::Field;
     use crate::fp::{FiniteField, Fp};
 
     #[test]
@@ -310,6 +379,76 @@ mod tests {
         assert_eq!(a / b, Fp(1))
     }
 
+    #[test]
+    fn test_bytes_round_trip() {
+        let a = Fp(0x0123456789abcdef);
+
+        assert_eq!(a.to_bytes(), 0x0123456789abcdefu64.to_le_bytes());
+        assert_eq!(Fp::from_bytes(a.to_bytes()), Some(a));
+        assert_eq!(Fp::from_bytes(Fp::MAX.to_bytes()), Some(Fp::MAX));
+    }
+
+    #[test]
+    fn test_bytes_canonical() {
+        assert_eq!(Fp(Fp::MODULO + 5).to_bytes(), Fp(5).to_bytes());
+        assert_eq!(Fp::from_bytes(Fp::MODULO.to_le_bytes()), None);
+        assert_eq!(Fp::from_bytes(u64::MAX.to_le_bytes()), None);
+    }
+
+    #[test]
+    fn test_neg() {
+        assert_eq!(-Fp(0), Fp(0));
+        assert_eq!(-Fp(Fp::MODULO), Fp(0));
+        assert_eq!(-Fp(1), Fp::MAX);
+        assert_eq!(-Fp(u64::MAX), Fp(Fp::MODULO - (u64::MAX - Fp::MODULO)));
+    }
+
+    #[test]
+    fn test_matches_u128_reference() {
+        let modulo = Fp::MODULO as u128;
+        let mut values = vec![
+            0,
+            1,
+            2,
+            (1 << 32) - 1,
+            1 << 32,
+            (1 << 32) + 1,
+            1 << 63,
+            Fp::MODULO - 1,
+            Fp::MODULO,
+            Fp::MODULO + 1,
+            u64::MAX - 1,
+            u64::MAX,
+        ];
+        values.extend((0..50).map(|_| rand::random::<u64>()));
+
+        for &a in &values {
+            for &b in &values {
+                let (x, y) = (a as u128, b as u128);
+
+                assert_eq!((Fp(a) + Fp(b)).0 as u128, (x + y) % modulo);
+                assert_eq!(
+                    (Fp(a) - Fp(b)).0 as u128,
+                    (x % modulo + modulo - y % modulo) % modulo
+                );
+                assert_eq!((Fp(a) * Fp(b)).0 as u128, (x * y) % modulo);
+            }
+        }
+    }
+
+    #[test]
+    fn test_inverse() {
+        assert_eq!(Fp(0).inverse(), Fp(0));
+        assert_eq!(Fp(1).inverse(), Fp(1));
+        assert_eq!(Fp(2).inverse() * Fp(2), Fp(1));
+        assert_eq!(Fp::MAX.inverse(), Fp::MAX);
+
+        for _ in 0..100 {
+            let a = Fp::sample();
+            assert_eq!(a * a.inverse(), Fp(1));
+        }
+    }
+
     #[test]
     fn test_pow() {
         let a = Fp(2);
@@ -317,4 +456,21 @@ mod tests {
 
         assert_eq!(a.pow(b), Fp(1024))
     }
+
+    #[test]
+    fn test_two_adic_root_of_unity() {
+        assert_eq!(
+            Fp::TWO_ADIC_ROOT_OF_UNITY,
+            Fp::GENERATOR.pow((Fp::MODULO - 1) >> Fp::TWO_ADICITY)
+        );
+
+        for log_size in [0, 1, 5, Fp::TWO_ADICITY] {
+            let root = Fp::two_adic_root_of_unity(log_size);
+
+            assert_eq!(root.exp_power_of_2(log_size), Fp(1));
+            if log_size > 0 {
+                assert_eq!(root.exp_power_of_2(log_size - 1), -Fp(1));
+            }
+        }
+    }
 }
@@ -0,0 +1,186 @@
+use crate::field::{ExtensionField, Field};
+use crate::fp::Fp;
+use std::ops::{Add, Div, Mul, Neg, Sub, SubAssign};
+
+/// Quadratic extension `Fp[u] / (u^2 - 7)`, the element `a + b * u` is `Fp2(a, b)`.
+///
+/// 7 is a quadratic non-residue mod the Goldilocks prime, so `u^2 - 7` is irreducible.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Fp2(pub Fp, pub Fp);
+
+impl Fp2 {
+    pub const W: Fp = Fp(7);
+
+    /// `a^2 - 7 * b^2`, the product of the element with its conjugate.
+    pub fn norm(&self) -> Fp {
+        self.0 * self.0 - Self::W * self.1 * self.1
+    }
+}
+
+impl Field for Fp2 {
+    const NUM_BYTES: usize = 16;
+
+    fn zero() -> Self {
+        Fp2(Fp::zero(), Fp::zero())
+    }
+
+    fn one() -> Self {
+        Fp2(Fp::one(), Fp::zero())
+    }
+
+    fn from_u64(value: u64) -> Self {
+        Self::from_base(Fp::from(value))
+    }
+
+    /// `(a - b * u) / (a^2 - 7 * b^2)`
+    fn inverse(&self) -> Self {
+        let norm_inv = self.norm().inverse();
+
+        Fp2(self.0 * norm_inv, -self.1 * norm_inv)
+    }
+
+    fn sample() -> Self {
+        Fp2(Fp::sample(), Fp::sample())
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        [self.0.to_bytes(), self.1.to_bytes()].concat()
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() != Self::NUM_BYTES {
+            return None;
+        }
+
+        let (a, b) = bytes.split_at(8);
+
+        Some(Fp2(
+            Fp::from_bytes(a.try_into().ok()?)?,
+            Fp::from_bytes(b.try_into().ok()?)?,
+        ))
+    }
+}
+
+impl ExtensionField<Fp> for Fp2 {
+    const DEGREE: usize = 2;
+
+    fn from_base(value: Fp) -> Self {
+        Fp2(value, Fp::zero())
+    }
+
+    /// `u^p = 7^((p - 1) / 2) * u = -u`, so the Frobenius map is conjugation.
+    fn frobenius(&self) -> Self {
+        Fp2(self.0, -self.1)
+    }
+}
+
+impl Add for Fp2 {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Fp2(self.0 + rhs.0, self.1 + rhs.1)
+    }
+}
+
+impl Sub for Fp2 {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Fp2(self.0 - rhs.0, self.1 - rhs.1)
+    }
+}
+
+impl SubAssign for Fp2 {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul for Fp2 {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Fp2(
+            self.0 * rhs.0 + Self::W * self.1 * rhs.1,
+            self.0 * rhs.1 + self.1 * rhs.0,
+        )
+    }
+}
+
+impl Mul<Fp> for Fp2 {
+    type Output = Self;
+
+    fn mul(self, rhs: Fp) -> Self::Output {
+        Fp2(self.0 * rhs, self.1 * rhs)
+    }
+}
+
+impl Div for Fp2 {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self::Output {
+        self * rhs.inverse()
+    }
+}
+
+impl Neg for Fp2 {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Fp2(-self.0, -self.1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::field::{ExtensionField, Field};
+    use crate::fp::{FiniteField, Fp};
+    use crate::fp2::Fp2;
+
+    #[test]
+    fn test_non_residue() {
+        assert_eq!(Fp2::W.pow((Fp::MODULO - 1) / 2), -Fp(1));
+    }
+
+    #[test]
+    fn test_mul() {
+        let u = Fp2(Fp(0), Fp(1));
+
+        assert_eq!(u * u, Fp2::from_base(Fp(7)));
+        assert_eq!(
+            Fp2(Fp(1), Fp(2)) * Fp2(Fp(3), Fp(4)),
+            Fp2(Fp(3 + 7 * 8), Fp(10))
+        );
+        assert_eq!(Fp2(Fp(1), Fp(2)) * Fp(3), Fp2(Fp(3), Fp(6)));
+    }
+
+    #[test]
+    fn test_inverse() {
+        assert_eq!(Fp2::zero().inverse(), Fp2::zero());
+
+        for _ in 0..100 {
+            let a = Fp2::sample();
+            assert_eq!(a * a.inverse(), Fp2::one());
+            assert_eq!(a / a, Fp2::one());
+        }
+    }
+
+    #[test]
+    fn test_frobenius() {
+        let a = Fp2::sample();
+
+        assert_eq!(a.frobenius(), a.pow(Fp::MODULO));
+        assert_eq!(a.frobenius().frobenius(), a);
+        assert_eq!(Fp2::from_base(a.norm()), a * a.frobenius());
+    }
+
+    #[test]
+    fn test_bytes_round_trip() {
+        let a = Fp2::sample();
+
+        assert_eq!(Fp2::from_bytes(&a.to_bytes()), Some(a));
+        assert_eq!(Fp2::from_bytes(&a.to_bytes()[1..]), None);
+        assert_eq!(Fp2::from_bytes(&[0xff; 16]), None);
+    }
+}
@@ -0,0 +1,199 @@
+use crate::field::{ExtensionField, Field};
+use crate::fp::Fp;
+use std::ops::{Add, Div, Mul, Neg, Sub, SubAssign};
+
+/// Cubic extension `Fp[u] / (u^3 - 7)`, the element `a + b * u + c * u^2` is `Fp3(a, b, c)`.
+///
+/// 7 is not a cube mod the Goldilocks prime, so `u^3 - 7` is irreducible.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Fp3(pub Fp, pub Fp, pub Fp);
+
+impl Fp3 {
+    pub const W: Fp = Fp(7);
+
+    /// `7^((p - 1) / 3)`, a primitive cube root of unity with `u^p = Z * u`.
+    pub const Z: Fp = Fp(18446744065119617025);
+
+    /// Product of the three Galois conjugates, always an element of `Fp`.
+    pub fn norm(&self) -> Fp {
+        let conjugate = self.frobenius();
+
+        (*self * conjugate * conjugate.frobenius()).0
+    }
+}
+
+impl Field for Fp3 {
+    const NUM_BYTES: usize = 24;
+
+    fn zero() -> Self {
+        Fp3(Fp::zero(), Fp::zero(), Fp::zero())
+    }
+
+    fn one() -> Self {
+        Fp3(Fp::one(), Fp::zero(), Fp::zero())
+    }
+
+    fn from_u64(value: u64) -> Self {
+        Self::from_base(Fp::from(value))
+    }
+
+    /// `x^-1 = x^p * x^(p^2) / N(x)`, the conjugates multiply to the norm.
+    fn inverse(&self) -> Self {
+        let first = self.frobenius();
+        let adjugate = first * first.frobenius();
+        let norm = (*self * adjugate).0;
+
+        adjugate * norm.inverse()
+    }
+
+    fn sample() -> Self {
+        Fp3(Fp::sample(), Fp::sample(), Fp::sample())
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        [self.0.to_bytes(), self.1.to_bytes(), self.2.to_bytes()].concat()
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() != Self::NUM_BYTES {
+            return None;
+        }
+
+        let mut chunks = bytes
+            .chunks(8)
+            .map(|chunk| Fp::from_bytes(chunk.try_into().ok()?));
+
+        Some(Fp3(chunks.next()??, chunks.next()??, chunks.next()??))
+    }
+}
+
+impl ExtensionField<Fp> for Fp3 {
+    const DEGREE: usize = 3;
+
+    fn from_base(value: Fp) -> Self {
+        Fp3(value, Fp::zero(), Fp::zero())
+    }
+
+    /// `(a + b * u + c * u^2)^p = a + b * Z * u + c * Z^2 * u^2`
+    fn frobenius(&self) -> Self {
+        Fp3(self.0, self.1 * Self::Z, self.2 * Self::Z * Self::Z)
+    }
+}
+
+impl Add for Fp3 {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Fp3(self.0 + rhs.0, self.1 + rhs.1, self.2 + rhs.2)
+    }
+}
+
+impl Sub for Fp3 {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Fp3(self.0 - rhs.0, self.1 - rhs.1, self.2 - rhs.2)
+    }
+}
+
+impl SubAssign for Fp3 {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul for Fp3 {
+    type Output = Self;
+
+    /// Schoolbook product, `u^3` and `u^4` reduce to `7` and `7 * u`.
+    fn mul(self, rhs: Self) -> Self::Output {
+        let Fp3(a0, a1, a2) = self;
+        let Fp3(b0, b1, b2) = rhs;
+
+        Fp3(
+            a0 * b0 + Self::W * (a1 * b2 + a2 * b1),
+            a0 * b1 + a1 * b0 + Self::W * a2 * b2,
+            a0 * b2 + a1 * b1 + a2 * b0,
+        )
+    }
+}
+
+impl Mul<Fp> for Fp3 {
+    type Output = Self;
+
+    fn mul(self, rhs: Fp) -> Self::Output {
+        Fp3(self.0 * rhs, self.1 * rhs, self.2 * rhs)
+    }
+}
+
+impl Div for Fp3 {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self::Output {
+        self * rhs.inverse()
+    }
+}
+
+impl Neg for Fp3 {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Fp3(-self.0, -self.1, -self.2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::field::{ExtensionField, Field};
+    use crate::fp::{FiniteField, Fp};
+    use crate::fp3::Fp3;
+
+    #[test]
+    fn test_non_cube() {
+        assert_eq!(Fp3::W.pow((Fp::MODULO - 1) / 3), Fp3::Z);
+        assert_ne!(Fp3::Z, Fp(1));
+        assert_eq!(Fp3::Z.pow(3), Fp(1));
+    }
+
+    #[test]
+    fn test_mul() {
+        let u = Fp3(Fp(0), Fp(1), Fp(0));
+
+        assert_eq!(u * u, Fp3(Fp(0), Fp(0), Fp(1)));
+        assert_eq!(u * u * u, Fp3::from_base(Fp(7)));
+        assert_eq!(Fp3(Fp(1), Fp(2), Fp(3)) * Fp(2), Fp3(Fp(2), Fp(4), Fp(6)));
+    }
+
+    #[test]
+    fn test_inverse() {
+        assert_eq!(Fp3::zero().inverse(), Fp3::zero());
+
+        for _ in 0..100 {
+            let a = Fp3::sample();
+            assert_eq!(a * a.inverse(), Fp3::one());
+            assert_eq!(a / a, Fp3::one());
+        }
+    }
+
+    #[test]
+    fn test_frobenius() {
+        let a = Fp3::sample();
+
+        assert_eq!(a.frobenius(), a.pow(Fp::MODULO));
+        assert_eq!(a.frobenius().frobenius().frobenius(), a);
+        assert_eq!(
+            Fp3::from_base(a.norm()),
+            a * a.frobenius() * a.frobenius().frobenius()
+        );
+    }
+
+    #[test]
+    fn test_bytes_round_trip() {
+        let a = Fp3::sample();
+
+        assert_eq!(Fp3::from_bytes(&a.to_bytes()), Some(a));
+        assert_eq!(Fp3::from_bytes(&a.to_bytes()[..16]), None);
+        assert_eq!(Fp3::from_bytes(&[0xff; 24]), None);
+    }
+}
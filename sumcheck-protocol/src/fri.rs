@@ -0,0 +1,425 @@
+use crate::error::FriError;
+use crate::field::Field;
+use crate::fp::{FiniteField, Fp};
+use crate::merkle::{Hash, MerkleTree};
+use crate::ntt::coset_ntt;
+use crate::transcript::Transcript;
+use crate::upolynomial::UPolynomial;
+
+/// FRI low-degree test for evaluations over the coset `GENERATOR * <w>` of `Fp`.
+///
+/// A polynomial of degree below `degree_bound` is evaluated on a domain of size
+/// `blowup * degree_bound`. Every round commits the current layer in a Merkle tree,
+/// with `f(x)` and `f(-x)` in one leaf, and folds it into
+/// `f'(x^2) = (f(x) + f(-x)) / 2 + beta * (f(x) - f(-x)) / 2x`,
+/// which halves both the domain and the degree bound. Once the degree bound reaches
+/// one the layer is committed as well and its constant value is sent in the clear.
+/// Random queries check every fold along a path through the layers, and that the
+/// pair opened in the last layer is that constant.
+#[derive(Debug, Clone, Copy)]
+pub struct Fri {
+    blowup: usize,
+    number_of_queries: usize,
+}
+
+/// Pair `f(x), f(-x)` of one layer and its Merkle path.
+#[derive(Debug, Clone)]
+pub struct FriLayerOpening {
+    pub evaluations: [Fp; 2],
+    pub path: Vec<Hash>,
+}
+
+#[derive(Debug, Clone)]
+pub struct FriProof {
+    pub layer_roots: Vec<Hash>,
+    pub final_value: Fp,
+    /// One opening per layer for every query.
+    pub queries: Vec<Vec<FriLayerOpening>>,
+}
+
+impl Default for Fri {
+    fn default() -> Self {
+        Self::new(4, 32)
+    }
+}
+
+impl Fri {
+    pub fn new(blowup: usize, number_of_queries: usize) -> Self {
+        assert!(
+            blowup >= 2 && blowup.is_power_of_two(),
+            "blowup must be a power of two of at least 2"
+        );
+        assert!(number_of_queries > 0, "at least one query is needed");
+
+        Self {
+            blowup,
+            number_of_queries,
+        }
+    }
+
+    pub fn blowup(&self) -> usize {
+        self.blowup
+    }
+
+    /// Evaluation domain for polynomials of degree below `degree_bound`.
+    pub fn domain(&self, degree_bound: usize) -> Vec<Fp> {
+        assert!(degree_bound.is_power_of_two());
+
+        coset(Fp::GENERATOR, self.blowup * degree_bound)
+    }
+
+    /// Evaluations of `polynomial` over `domain(degree_bound)`.
+    pub fn evaluate(&self, polynomial: &UPolynomial, degree_bound: usize) -> Vec<Fp> {
+        assert!(polynomial.coefficients.len() <= degree_bound);
+        assert!(degree_bound.is_power_of_two());
+
+        let mut evaluations = polynomial.coefficients.clone();
+        evaluations.resize(self.blowup * degree_bound, Fp::zero());
+
+        coset_ntt(&mut evaluations, Fp::GENERATOR);
+
+        evaluations
+    }
+
+    /// Proves that `evaluations` over `domain(evaluations.len() / blowup)` are close
+    /// to a polynomial of degree below `evaluations.len() / blowup`.
+    pub fn prove(&self, evaluations: Vec<Fp>, transcript: &mut Transcript) -> FriProof {
+        assert!(
+            evaluations.len().is_power_of_two() && evaluations.len() >= self.blowup,
+            "evaluations must cover a domain of a power of two size of at least blowup"
+        );
+
+        transcript.absorb_u64(b"fri_domain_size", evaluations.len() as u64);
+
+        let mut layer = evaluations;
+        let mut offset = Fp::GENERATOR;
+        let mut layers = vec![];
+
+        while layer.len() > self.blowup {
+            let half = layer.len() / 2;
+
+            let tree = MerkleTree::from(
+                (0..half)
+                    .map(|i| hash_pair(&[layer[i], layer[i + half]]))
+                    .collect(),
+            );
+            transcript.absorb_bytes(b"fri_layer_root", &tree.root());
+
+            let beta: Fp = transcript.challenge(b"fri_beta");
+            let inverses = Fp::multi_inv(&coset(offset, layer.len())[..half]);
+
+            let folded = (0..half)
+                .map(|i| fold(&[layer[i], layer[i + half]], beta, inverses[i]))
+                .collect();
+
+            layers.push((std::mem::replace(&mut layer, folded), tree));
+            offset = offset * offset;
+        }
+
+        let bound = layer.len() << layers.len();
+
+        let half = layer.len() / 2;
+        let tree = MerkleTree::from(
+            (0..half)
+                .map(|i| hash_pair(&[layer[i], layer[i + half]]))
+                .collect(),
+        );
+        transcript.absorb_bytes(b"fri_layer_root", &tree.root());
+
+        let final_value = layer[0];
+        transcript.absorb_field(b"fri_final_value", &final_value);
+        layers.push((layer, tree));
+
+        let queries = (0..self.number_of_queries)
+            .map(|_| {
+                let mut index = transcript.challenge_index(b"fri_query", bound / 2);
+
+                layers
+                    .iter()
+                    .map(|(layer, tree)| {
+                        let half = layer.len() / 2;
+                        index %= half;
+
+                        FriLayerOpening {
+                            evaluations: [layer[index], layer[index + half]],
+                            path: tree.open(index),
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+
+        FriProof {
+            layer_roots: layers.iter().map(|(_, tree)| tree.root()).collect(),
+            final_value,
+            queries,
+        }
+    }
+
+    /// Proves that `polynomial` has degree below the next power of two of its length.
+    pub fn prove_polynomial(
+        &self,
+        polynomial: &UPolynomial,
+        transcript: &mut Transcript,
+    ) -> FriProof {
+        let degree_bound = polynomial.coefficients.len().next_power_of_two();
+
+        self.prove(self.evaluate(polynomial, degree_bound), transcript)
+    }
+
+    /// Checks a proof for degree below `degree_bound`, the proof commits one layer per
+    /// fold and the constant last layer.
+    ///
+    /// Returns the queried indices `i`, the first opening of each query holds the
+    /// committed evaluations at `domain[i]` and `domain[i + domain.len() / 2]`.
+    pub fn verify(
+        &self,
+        degree_bound: usize,
+        proof: &FriProof,
+        transcript: &mut Transcript,
+    ) -> Result<Vec<usize>, FriError> {
+        assert!(degree_bound.is_power_of_two());
+
+        let number_of_layers = degree_bound.trailing_zeros() as usize + 1;
+        let domain_size = self.blowup * degree_bound;
+
+        if proof.layer_roots.len() != number_of_layers {
+            return Err(FriError::WrongNumberOfLayers {
+                expected: number_of_layers,
+                received: proof.layer_roots.len(),
+            });
+        }
+
+        if proof.queries.len() != self.number_of_queries {
+            return Err(FriError::WrongNumberOfQueries {
+                expected: self.number_of_queries,
+                received: proof.queries.len(),
+            });
+        }
+
+        transcript.absorb_u64(b"fri_domain_size", domain_size as u64);
+
+        let (last_root, folded_roots) = proof.layer_roots.split_last().unwrap();
+        let betas: Vec<Fp> = folded_roots
+            .iter()
+            .map(|root| {
+                transcript.absorb_bytes(b"fri_layer_root", root);
+                transcript.challenge(b"fri_beta")
+            })
+            .collect();
+
+        transcript.absorb_bytes(b"fri_layer_root", last_root);
+        transcript.absorb_field(b"fri_final_value", &proof.final_value);
+
+        let mut indices = vec![];
+
+        for (query, openings) in proof.queries.iter().enumerate() {
+            if openings.len() != number_of_layers {
+                return Err(FriError::WrongNumberOfLayers {
+                    expected: number_of_layers,
+                    received: openings.len(),
+                });
+            }
+
+            let mut index = transcript.challenge_index(b"fri_query", domain_size / 2);
+            let mut size = domain_size;
+            let mut offset = Fp::GENERATOR;
+            let mut expected = None;
+
+            indices.push(index);
+
+            for (layer, opening) in openings.iter().enumerate() {
+                let half = size / 2;
+                let (leaf, slot) = (index % half, index / half);
+
+                if expected.is_some_and(|value| opening.evaluations[slot] != value) {
+                    return Err(FriError::FoldingMismatch {
+                        query,
+                        layer: layer - 1,
+                    });
+                }
+
+                let leaf_hash = hash_pair(&opening.evaluations);
+                if !MerkleTree::verify(&proof.layer_roots[layer], leaf, &leaf_hash, &opening.path) {
+                    return Err(FriError::InvalidPath { query, layer });
+                }
+
+                if layer == betas.len() {
+                    if opening.evaluations != [proof.final_value; 2] {
+                        return Err(FriError::FinalValueMismatch { query });
+                    }
+                    break;
+                }
+
+                let x = offset
+                    * Fp::two_adic_root_of_unity(size.trailing_zeros() as usize).pow(leaf as u64);
+                expected = Some(fold(&opening.evaluations, betas[layer], x.inverse()));
+
+                index = leaf;
+                size = half;
+                offset = offset * offset;
+            }
+        }
+
+        Ok(indices)
+    }
+}
+
+/// `offset * w^i` for the `size`-th root of unity `w`.
+fn coset(offset: Fp, size: usize) -> Vec<Fp> {
+    let root = Fp::two_adic_root_of_unity(size.trailing_zeros() as usize);
+
+    (0..size)
+        .scan(offset, |x, _| {
+            let current = *x;
+            *x = *x * root;
+
+            Some(current)
+        })
+        .collect()
+}
+
+fn hash_pair(pair: &[Fp; 2]) -> Hash {
+    let mut bytes = pair[0].to_bytes().to_vec();
+    bytes.extend_from_slice(&pair[1].to_bytes());
+
+    MerkleTree::hash_leaf(&bytes)
+}
+
+/// `(f(x) + f(-x)) / 2 + beta * (f(x) - f(-x)) / 2x` from `f(x), f(-x)` and `1 / x`.
+fn fold(pair: &[Fp; 2], beta: Fp, x_inverse: Fp) -> Fp {
+    let [a, b] = *pair;
+    // 2 * (p + 1) / 2 = 1 mod p
+    let half = Fp(Fp::MODULO.div_ceil(2));
+
+    half * ((a + b) + beta * x_inverse * (a - b))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::FriError;
+    use crate::fp::Fp;
+    use crate::fri::{coset, Fri};
+    use crate::transcript::Transcript;
+    use crate::upolynomial::UPolynomial;
+
+    fn polynomial(len: u64) -> UPolynomial {
+        UPolynomial::from((0..len).map(|i| Fp(i * i + 5)).collect())
+    }
+
+    #[test]
+    fn test_coset() {
+        let domain = coset(Fp::GENERATOR, 8);
+
+        assert_eq!(domain[0], Fp::GENERATOR);
+        // The second half is the negation of the first one
+        for i in 0..4 {
+            assert_eq!(domain[i + 4], -domain[i]);
+        }
+    }
+
+    #[test]
+    fn test_low_degree_is_accepted() {
+        let fri = Fri::default();
+
+        for len in [1, 2, 5, 16] {
+            let proof = fri.prove_polynomial(&polynomial(len), &mut Transcript::new(b"test"));
+            let degree_bound = (len as usize).next_power_of_two();
+
+            let indices = fri
+                .verify(degree_bound, &proof, &mut Transcript::new(b"test"))
+                .unwrap();
+            assert_eq!(indices.len(), 32);
+
+            let evaluations = fri.evaluate(&polynomial(len), degree_bound);
+            let half = evaluations.len() / 2;
+            let opening = &proof.queries[0][0];
+
+            assert_eq!(opening.evaluations[0], evaluations[indices[0]]);
+            assert_eq!(opening.evaluations[1], evaluations[indices[0] + half]);
+        }
+    }
+
+    #[test]
+    fn test_non_constant_last_layer_is_rejected() {
+        let fri = Fri::default();
+
+        // Degree 1 on the domain of degree bound 1, nothing is folded
+        let evaluations = fri.evaluate(&polynomial(2), 2)[..]
+            .iter()
+            .step_by(2)
+            .copied()
+            .collect::<Vec<_>>();
+        assert_eq!(evaluations.len(), 4);
+
+        let proof = fri.prove(evaluations, &mut Transcript::new(b"test"));
+        assert_eq!(proof.layer_roots.len(), 1);
+        assert!(matches!(
+            fri.verify(1, &proof, &mut Transcript::new(b"test")),
+            Err(FriError::FinalValueMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_high_degree_is_rejected() {
+        let fri = Fri::default();
+
+        // Degree 40 on the domain of degree bound 16
+        let evaluations = fri.evaluate(&polynomial(41), 64)[..]
+            .iter()
+            .step_by(4)
+            .copied()
+            .collect::<Vec<_>>();
+        assert_eq!(evaluations.len(), 64);
+
+        let proof = fri.prove(evaluations, &mut Transcript::new(b"test"));
+        assert!(matches!(
+            fri.verify(16, &proof, &mut Transcript::new(b"test")),
+            Err(FriError::FinalValueMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_tampered_proof_is_rejected() {
+        let fri = Fri::new(2, 8);
+        let proof = fri.prove_polynomial(&polynomial(8), &mut Transcript::new(b"test"));
+        let verify = |proof| fri.verify(8, proof, &mut Transcript::new(b"test"));
+
+        let mut tampered = proof.clone();
+        tampered.queries[1][2].evaluations[0] = tampered.queries[1][2].evaluations[0] + Fp(1);
+        assert!(matches!(
+            verify(&tampered),
+            Err(FriError::FoldingMismatch { query: 1, layer: 1 })
+                | Err(FriError::InvalidPath { query: 1, layer: 2 })
+        ));
+
+        let mut tampered = proof.clone();
+        tampered.queries[0][0].path.resize(64, [0; 32]);
+        assert_eq!(
+            verify(&tampered),
+            Err(FriError::InvalidPath { query: 0, layer: 0 })
+        );
+
+        let mut tampered = proof.clone();
+        tampered.final_value = tampered.final_value + Fp(1);
+        assert!(verify(&tampered).is_err());
+
+        let mut tampered = proof.clone();
+        tampered.queries.pop();
+        assert_eq!(
+            verify(&tampered),
+            Err(FriError::WrongNumberOfQueries {
+                expected: 8,
+                received: 7
+            })
+        );
+
+        assert_eq!(
+            fri.verify(4, &proof, &mut Transcript::new(b"test")),
+            Err(FriError::WrongNumberOfLayers {
+                expected: 3,
+                received: 4
+            })
+        );
+    }
+}
@@ -0,0 +1,578 @@
+use crate::error::GkrError;
+use crate::field::Field;
+use crate::fp::Fp;
+use crate::mle::{eq_table, DenseMultilinearExtension};
+use crate::proof::prove_rounds;
+use crate::prover::Prover;
+use crate::round_message::RoundMessage;
+use crate::transcript::Transcript;
+use crate::verifier::SumcheckVerifier;
+
+/// Gate reading two values of the layer below it by index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Gate {
+    Add(usize, usize),
+    Mul(usize, usize),
+}
+
+impl Gate {
+    fn inputs(&self) -> (usize, usize) {
+        match *self {
+            Gate::Add(left, right) | Gate::Mul(left, right) => (left, right),
+        }
+    }
+
+    fn apply<F: Field>(&self, left: F, right: F) -> F {
+        match self {
+            Gate::Add(..) => left + right,
+            Gate::Mul(..) => left * right,
+        }
+    }
+}
+
+/// Layered arithmetic circuit.
+///
+/// `layers[0]` computes the outputs, the gates of `layers[i]` read from `layers[i + 1]`
+/// and the gates of the last layer read the inputs.
+#[derive(Debug, Clone)]
+pub struct Circuit {
+    number_of_inputs: usize,
+    layers: Vec<Vec<Gate>>,
+}
+
+impl Circuit {
+    pub fn from(number_of_inputs: usize, layers: Vec<Vec<Gate>>) -> Self {
+        assert!(!layers.is_empty(), "circuit needs at least one layer");
+
+        for (i, layer) in layers.iter().enumerate() {
+            assert!(!layer.is_empty(), "layer {} has no gates", i);
+
+            let width = layers.get(i + 1).map_or(number_of_inputs, Vec::len);
+
+            for gate in layer {
+                let (left, right) = gate.inputs();
+                assert!(
+                    left < width && right < width,
+                    "gate of layer {} reads a missing wire",
+                    i
+                );
+            }
+        }
+
+        Self {
+            number_of_inputs,
+            layers,
+        }
+    }
+
+    pub fn number_of_inputs(&self) -> usize {
+        self.number_of_inputs
+    }
+
+    pub fn number_of_layers(&self) -> usize {
+        self.layers.len()
+    }
+
+    /// Values of every layer, the outputs first and the inputs last.
+    pub fn evaluate<F: Field>(&self, inputs: &[F]) -> Vec<Vec<F>> {
+        assert_eq!(inputs.len(), self.number_of_inputs);
+
+        let mut values = vec![inputs.to_vec()];
+
+        for layer in self.layers.iter().rev() {
+            let below = values.last().unwrap();
+            let layer_values = layer
+                .iter()
+                .map(|gate| {
+                    let (left, right) = gate.inputs();
+                    gate.apply(below[left], below[right])
+                })
+                .collect();
+
+            values.push(layer_values);
+        }
+
+        values.reverse();
+        values
+    }
+}
+
+/// Sumcheck of one layer, reducing a claim on its values to two claims on the layer below.
+#[derive(Debug, Clone)]
+pub struct LayerProof<F: Field = Fp> {
+    pub round_messages: Vec<RoundMessage<F>>,
+    /// Value of the layer below at the first half of the sumcheck point.
+    pub left: F,
+    /// Value of the layer below at the second half of the sumcheck point.
+    pub right: F,
+}
+
+#[derive(Debug, Clone)]
+pub struct GkrProof<F: Field = Fp> {
+    pub outputs: Vec<F>,
+    pub layers: Vec<LayerProof<F>>,
+}
+
+/// Number of variables of a layer with `width` values, at least one so every
+/// sumcheck has a round.
+fn number_of_vars(width: usize) -> usize {
+    width.next_power_of_two().trailing_zeros().max(1) as usize
+}
+
+/// Multilinear extension of a layer, padded with zeros to the full hypercube.
+fn extension<F: Field>(values: &[F]) -> DenseMultilinearExtension<F> {
+    let mut evaluations = values.to_vec();
+    evaluations.resize(1 << number_of_vars(values.len()), F::zero());
+
+    DenseMultilinearExtension::from(evaluations)
+}
+
+/// Binds the circuit, its inputs and the claimed outputs before any challenge is drawn.
+fn new_transcript<F: Field>(circuit: &Circuit, inputs: &[F], outputs: &[F]) -> Transcript {
+    let mut transcript = Transcript::new(b"gkr");
+
+    transcript.absorb_u64(b"number_of_inputs", circuit.number_of_inputs as u64);
+    for layer in &circuit.layers {
+        let mut wiring = vec![];
+
+        for gate in layer {
+            let (left, right) = gate.inputs();
+
+            wiring.push(matches!(gate, Gate::Mul(..)) as u8);
+            wiring.extend_from_slice(&(left as u64).to_le_bytes());
+            wiring.extend_from_slice(&(right as u64).to_le_bytes());
+        }
+
+        transcript.absorb_bytes(b"layer", &wiring);
+    }
+
+    transcript.absorb_fields(b"inputs", inputs);
+    transcript.absorb_fields(b"outputs", outputs);
+
+    transcript
+}
+
+fn challenges<F: Field>(transcript: &mut Transcript, count: usize) -> Vec<F> {
+    (0..count)
+        .map(|_| transcript.challenge(b"challenge"))
+        .collect()
+}
+
+/// `eq(u, .) + alpha * eq(v, .)`, folds the two claims on a layer into one.
+fn combine<F: Field>(u: &[F], v: &[F], alpha: F) -> Vec<F> {
+    eq_table(u)
+        .into_iter()
+        .zip(eq_table(v))
+        .map(|(at_u, at_v)| at_u + alpha * at_v)
+        .collect()
+}
+
+/// Evaluation of `add(g, u, v) * (left + right) + mul(g, u, v) * left * right`, the
+/// polynomial a layer sumcheck ends on, with `g` given by the weights of the gates.
+fn wiring_evaluation<F: Field>(gates: &[Gate], weights: &[F], point: &[F], left: F, right: F) -> F {
+    let (u, v) = point.split_at(point.len() / 2);
+    let (eq_u, eq_v) = (eq_table(u), eq_table(v));
+
+    gates
+        .iter()
+        .zip(weights)
+        .fold(F::zero(), |acc, (gate, weight)| {
+            let (a, b) = gate.inputs();
+            acc + *weight * eq_u[a] * eq_v[b] * gate.apply(left, right)
+        })
+}
+
+/// Sums `add(g, x, y) * (W(x) + W(y)) + mul(g, x, y) * W(x) * W(y)` over `x`, then `y`.
+///
+/// `g` only enters through `weights[z]`, the weight of the claim on gate `z`. Both
+/// phases sum `p * q + r` over tables the size of the layer below, so no table
+/// over `(x, y)` is ever built.
+struct LayerProver<'a, F: Field> {
+    gates: &'a [Gate],
+    weights: Vec<F>,
+    below: DenseMultilinearExtension<F>,
+    number_of_vars: usize,
+    p: DenseMultilinearExtension<F>,
+    q: DenseMultilinearExtension<F>,
+    r: DenseMultilinearExtension<F>,
+    point: Vec<F>,
+    left: F,
+}
+
+impl<'a, F: Field> LayerProver<'a, F> {
+    fn new(gates: &'a [Gate], weights: Vec<F>, below: DenseMultilinearExtension<F>) -> Self {
+        let size = below.evaluations.len();
+        let mut q = vec![F::zero(); size];
+        let mut r = vec![F::zero(); size];
+
+        // Phase one: W(x) * (sum_y mul(g, x, y) W(y) + sum_y add(g, x, y)) + sum_y add(g, x, y) W(y)
+        for (gate, weight) in gates.iter().zip(&weights) {
+            match *gate {
+                Gate::Add(a, b) => {
+                    q[a] = q[a] + *weight;
+                    r[a] = r[a] + *weight * below.evaluations[b];
+                }
+                Gate::Mul(a, b) => {
+                    q[a] = q[a] + *weight * below.evaluations[b];
+                }
+            }
+        }
+
+        Self {
+            gates,
+            weights,
+            number_of_vars: below.number_of_vars(),
+            p: below.clone(),
+            q: DenseMultilinearExtension::from(q),
+            r: DenseMultilinearExtension::from(r),
+            below,
+            point: vec![],
+            left: F::zero(),
+        }
+    }
+
+    /// Phase two, with `x` fixed to `u`:
+    /// `W(y) * (W(u) * mul(g, u, y) + add(g, u, y)) + W(u) * add(g, u, y)`
+    fn start_second_phase(&mut self) {
+        self.left = self.p.evaluations[0];
+
+        let eq_u = eq_table(&self.point);
+        let size = self.below.evaluations.len();
+        let mut q = vec![F::zero(); size];
+        let mut r = vec![F::zero(); size];
+
+        for (gate, weight) in self.gates.iter().zip(&self.weights) {
+            match *gate {
+                Gate::Add(a, b) => {
+                    let c = *weight * eq_u[a];
+                    q[b] = q[b] + c;
+                    r[b] = r[b] + self.left * c;
+                }
+                Gate::Mul(a, b) => {
+                    q[b] = q[b] + self.left * *weight * eq_u[a];
+                }
+            }
+        }
+
+        self.p = self.below.clone();
+        self.q = DenseMultilinearExtension::from(q);
+        self.r = DenseMultilinearExtension::from(r);
+    }
+
+    /// Claims on the layer below at both halves of the sumcheck point.
+    fn claims(&self) -> (F, F) {
+        (self.left, self.p.evaluations[0])
+    }
+}
+
+impl<F: Field> Prover<F> for LayerProver<'_, F> {
+    fn claimed_sum(&self) -> F {
+        (0..self.p.evaluations.len()).fold(F::zero(), |acc, i| {
+            acc + self.p.evaluations[i] * self.q.evaluations[i] + self.r.evaluations[i]
+        })
+    }
+
+    fn number_of_vars(&self) -> usize {
+        2 * self.number_of_vars
+    }
+
+    fn degree(&self) -> usize {
+        2
+    }
+
+    fn round_evaluations(&mut self, challenge: Option<F>) -> Option<Vec<F>> {
+        if let Some(challenge) = challenge {
+            self.p.fix_first_variable(challenge);
+            self.q.fix_first_variable(challenge);
+            self.r.fix_first_variable(challenge);
+            self.point.push(challenge);
+
+            if self.point.len() == self.number_of_vars {
+                self.start_second_phase();
+            }
+        }
+
+        if self.point.len() == 2 * self.number_of_vars {
+            return None;
+        }
+
+        let mut evaluations = vec![F::zero(); 3];
+
+        for i in 0..self.p.evaluations.len() / 2 {
+            let mut p = self.p.evaluations[2 * i];
+            let mut q = self.q.evaluations[2 * i];
+            let mut r = self.r.evaluations[2 * i];
+
+            let p_step = self.p.evaluations[2 * i + 1] - p;
+            let q_step = self.q.evaluations[2 * i + 1] - q;
+            let r_step = self.r.evaluations[2 * i + 1] - r;
+
+            for evaluation in evaluations.iter_mut() {
+                *evaluation = *evaluation + p * q + r;
+
+                p = p + p_step;
+                q = q + q_step;
+                r = r + r_step;
+            }
+        }
+
+        Some(evaluations)
+    }
+
+    fn final_evaluation(&self) -> F {
+        self.p.evaluations[0] * self.q.evaluations[0] + self.r.evaluations[0]
+    }
+}
+
+/// Proves that `circuit` maps `inputs` to the outputs stored in the proof.
+pub fn prove_circuit<F: Field>(circuit: &Circuit, inputs: &[F]) -> GkrProof<F> {
+    let values = circuit.evaluate(inputs);
+    let outputs = values[0].clone();
+
+    let mut transcript = new_transcript(circuit, inputs, &outputs);
+    let mut weights = eq_table(&challenges(&mut transcript, number_of_vars(outputs.len())));
+
+    let mut layers = vec![];
+
+    for (i, gates) in circuit.layers.iter().enumerate() {
+        let mut prover = LayerProver::new(gates, weights, extension(&values[i + 1]));
+        let (round_messages, _) = prove_rounds(&mut prover, &mut transcript);
+
+        let (left, right) = prover.claims();
+        transcript.absorb_fields(b"claims", &[left, right]);
+        let alpha = transcript.challenge(b"alpha");
+
+        let (u, v) = prover.point.split_at(prover.number_of_vars);
+        weights = combine(u, v, alpha);
+
+        layers.push(LayerProof {
+            round_messages,
+            left,
+            right,
+        });
+    }
+
+    GkrProof { outputs, layers }
+}
+
+/// Checks `proof` layer by layer down to `inputs` and returns the proven outputs.
+pub fn verify_circuit<F: Field>(
+    circuit: &Circuit,
+    inputs: &[F],
+    proof: &GkrProof<F>,
+) -> Result<Vec<F>, GkrError> {
+    if inputs.len() != circuit.number_of_inputs {
+        return Err(GkrError::WrongNumberOfInputs {
+            expected: circuit.number_of_inputs,
+            received: inputs.len(),
+        });
+    }
+
+    if proof.outputs.len() != circuit.layers[0].len() {
+        return Err(GkrError::WrongNumberOfOutputs {
+            expected: circuit.layers[0].len(),
+            received: proof.outputs.len(),
+        });
+    }
+
+    if proof.layers.len() != circuit.layers.len() {
+        return Err(GkrError::WrongNumberOfLayers {
+            expected: circuit.layers.len(),
+            received: proof.layers.len(),
+        });
+    }
+
+    let mut transcript = new_transcript(circuit, inputs, &proof.outputs);
+
+    let r = challenges(&mut transcript, number_of_vars(proof.outputs.len()));
+    let mut claim = extension(&proof.outputs).eval(&r);
+    let mut weights = eq_table(&r);
+    let mut point = vec![];
+
+    for (i, (gates, layer)) in circuit.layers.iter().zip(&proof.layers).enumerate() {
+        let width = circuit
+            .layers
+            .get(i + 1)
+            .map_or(circuit.number_of_inputs, Vec::len);
+
+        point = {
+            let mut verifier = SumcheckVerifier::new(claim, 2 * number_of_vars(width), 2, |x| {
+                wiring_evaluation(gates, &weights, x, layer.left, layer.right)
+            });
+
+            verifier
+                .verify_rounds(&layer.round_messages, &mut transcript)
+                .map_err(|error| GkrError::Sumcheck { layer: i, error })?;
+
+            verifier.randomness().to_vec()
+        };
+
+        transcript.absorb_fields(b"claims", &[layer.left, layer.right]);
+        let alpha = transcript.challenge(b"alpha");
+
+        let (u, v) = point.split_at(point.len() / 2);
+        weights = combine(u, v, alpha);
+        claim = layer.left + alpha * layer.right;
+    }
+
+    let (u, v) = point.split_at(point.len() / 2);
+    let last = proof.layers.last().unwrap();
+    let inputs = extension(inputs);
+
+    if inputs.eval(u) != last.left || inputs.eval(v) != last.right {
+        return Err(GkrError::InputMismatch);
+    }
+
+    Ok(proof.outputs.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::{GkrError, SumcheckError};
+    use crate::field::Field;
+    use crate::fp::Fp;
+    use crate::fp3::Fp3;
+    use crate::gkr::{prove_circuit, verify_circuit, Circuit, Gate};
+
+    /// outputs: (a * b + (c + d), (c + d) * (b * d))
+    fn circuit() -> Circuit {
+        Circuit::from(
+            4,
+            vec![
+                vec![Gate::Add(0, 1), Gate::Mul(1, 2)],
+                vec![Gate::Mul(0, 1), Gate::Add(2, 3), Gate::Mul(1, 3)],
+            ],
+        )
+    }
+
+    fn inputs() -> Vec<Fp> {
+        vec![Fp(2), Fp(3), Fp(5), Fp(7)]
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let values = circuit().evaluate(&inputs());
+
+        assert_eq!(values.len(), 3);
+        assert_eq!(values[0], vec![Fp(6 + 12), Fp(12 * 21)]);
+        assert_eq!(values[1], vec![Fp(6), Fp(12), Fp(21)]);
+        assert_eq!(values[2], inputs());
+    }
+
+    #[test]
+    fn test_prove_and_verify() {
+        let circuit = circuit();
+        let proof = prove_circuit(&circuit, &inputs());
+
+        assert_eq!(proof.layers.len(), 2);
+        assert_eq!(
+            verify_circuit(&circuit, &inputs(), &proof),
+            Ok(vec![Fp(18), Fp(252)])
+        );
+    }
+
+    #[test]
+    fn test_deeper_circuit() {
+        let width = 8;
+        let layers = (0..5)
+            .map(|layer| {
+                (0..width)
+                    .map(|i| match (i + layer) % 3 {
+                        0 => Gate::Add(i, (i + 1) % width),
+                        1 => Gate::Mul(i, (3 * i + layer) % width),
+                        _ => Gate::Mul((i + 5) % width, (i + 5) % width),
+                    })
+                    .collect()
+            })
+            .collect();
+        let circuit = Circuit::from(width, layers);
+        let inputs: Vec<Fp> = (0..width as u64).map(|i| Fp(i + 1)).collect();
+
+        let proof = prove_circuit(&circuit, &inputs);
+
+        assert_eq!(
+            verify_circuit(&circuit, &inputs, &proof),
+            Ok(circuit.evaluate(&inputs)[0].clone())
+        );
+    }
+
+    #[test]
+    fn test_extension_field() {
+        let circuit = circuit();
+        let inputs: Vec<Fp3> = (0..4).map(|_| Fp3::sample()).collect();
+
+        let proof = prove_circuit(&circuit, &inputs);
+
+        assert!(verify_circuit(&circuit, &inputs, &proof).is_ok());
+    }
+
+    #[test]
+    fn test_reject_wrong_output() {
+        let circuit = circuit();
+        let mut proof = prove_circuit(&circuit, &inputs());
+
+        proof.outputs[1] = proof.outputs[1] + Fp(1);
+
+        assert!(verify_circuit(&circuit, &inputs(), &proof).is_err());
+    }
+
+    #[test]
+    fn test_reject_wrong_inputs() {
+        let circuit = circuit();
+        let proof = prove_circuit(&circuit, &inputs());
+
+        let other = vec![Fp(2), Fp(3), Fp(5), Fp(8)];
+
+        assert!(verify_circuit(&circuit, &other, &proof).is_err());
+    }
+
+    #[test]
+    fn test_reject_wrong_number_of_inputs() {
+        let circuit = circuit();
+        let proof = prove_circuit(&circuit, &inputs());
+
+        assert_eq!(
+            verify_circuit(&circuit, &inputs()[..3], &proof),
+            Err(GkrError::WrongNumberOfInputs {
+                expected: 4,
+                received: 3
+            })
+        );
+    }
+
+    #[test]
+    fn test_reject_tampered_layer_claim() {
+        let circuit = circuit();
+        let mut proof = prove_circuit(&circuit, &inputs());
+
+        proof.layers[0].right = proof.layers[0].right + Fp(1);
+
+        assert_eq!(
+            verify_circuit(&circuit, &inputs(), &proof),
+            Err(GkrError::Sumcheck {
+                layer: 0,
+                error: SumcheckError::FinalEvaluationMismatch
+            })
+        );
+    }
+
+    #[test]
+    fn test_reject_tampered_input_claim() {
+        let circuit = circuit();
+        let honest = prove_circuit(&circuit, &inputs());
+
+        let mut proof = honest.clone();
+        proof.layers.truncate(1);
+        assert_eq!(
+            verify_circuit(&circuit, &inputs(), &proof),
+            Err(GkrError::WrongNumberOfLayers {
+                expected: 2,
+                received: 1
+            })
+        );
+
+        let mut proof = honest;
+        proof.layers[1].left = proof.layers[1].left + Fp(1);
+        assert!(verify_circuit(&circuit, &inputs(), &proof).is_err());
+    }
+}
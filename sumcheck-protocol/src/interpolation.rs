@@ -0,0 +1,177 @@
+use crate::field::Field;
+use crate::fp::Fp;
+use crate::upolynomial::UPolynomial;
+
+/// Interpolation over the nodes `{0, 1, ..., degree}`, where round polynomials live.
+///
+/// The barycentric weight of node `i` among `{0, ..., k}` is
+/// `w_i = 1 / prod_{j != i} (i - j) = (-1)^(k - i) / (i! * (k - i)!)`,
+/// so the inverse factorials computed once here serve every `k <= degree`.
+#[derive(Debug, Clone)]
+pub struct InterpolationDomain<F: Field = Fp> {
+    inverse_factorials: Vec<F>,
+}
+
+impl<F: Field> InterpolationDomain<F> {
+    pub fn new(degree: usize) -> Self {
+        let mut factorials = vec![F::one()];
+        for i in 1..=degree as u64 {
+            factorials.push(factorials[i as usize - 1] * F::from_u64(i));
+        }
+
+        let mut inverse_factorials = vec![factorials[degree].inverse()];
+        for i in (1..=degree as u64).rev() {
+            inverse_factorials.push(*inverse_factorials.last().unwrap() * F::from_u64(i));
+        }
+        inverse_factorials.reverse();
+
+        Self { inverse_factorials }
+    }
+
+    /// Largest degree the domain can handle.
+    pub fn degree(&self) -> usize {
+        self.inverse_factorials.len() - 1
+    }
+
+    /// Barycentric weights of the nodes `{0, ..., k}`.
+    pub fn weights(&self, k: usize) -> Vec<F> {
+        assert!(k <= self.degree(), "domain is too small");
+
+        (0..=k)
+            .map(|i| {
+                let weight = self.inverse_factorials[i] * self.inverse_factorials[k - i];
+
+                if (k - i) % 2 == 1 {
+                    -weight
+                } else {
+                    weight
+                }
+            })
+            .collect()
+    }
+
+    /// Value at `x` of the polynomial through `(i, evaluations[i])`, in `O(k)` without
+    /// building its coefficients.
+    ///
+    /// With `l(x) = (x - 0)(x - 1)...(x - k)` the value is `l(x) * sum_i w_i * evaluations[i] / (x - i)`.
+    pub fn evaluate(&self, evaluations: &[F], x: F) -> F {
+        let k = evaluations.len() - 1;
+        let weights = self.weights(k);
+
+        let differences: Vec<F> = (0..=k as u64).map(|i| x - F::from_u64(i)).collect();
+
+        // Batch inversion would hide a zero difference, x is then a node
+        if let Some(i) = differences.iter().position(|d| *d == F::zero()) {
+            return evaluations[i];
+        }
+
+        let inverses = F::multi_inv(&differences);
+
+        let mut sum = F::zero();
+        let mut l = F::one();
+        for i in 0..=k {
+            sum = sum + weights[i] * evaluations[i] * inverses[i];
+            l = l * differences[i];
+        }
+
+        l * sum
+    }
+
+    /// Coefficients of the polynomial through `(i, evaluations[i])`, with `evaluations.len()`
+    /// coefficients even if the top ones are zero.
+    ///
+    /// Uses Newton's forward differences, `p(x) = sum_j (D^j y_0 / j!) * x(x - 1)...(x - j + 1)`,
+    /// which only needs the precomputed inverse factorials.
+    pub fn interpolate(&self, evaluations: &[F]) -> UPolynomial<F> {
+        let k = evaluations.len() - 1;
+        assert!(k <= self.degree(), "domain is too small");
+
+        let mut differences = evaluations.to_vec();
+        let mut newton = vec![];
+        for j in 0..=k {
+            newton.push(differences[0] * self.inverse_factorials[j]);
+
+            differences = differences.windows(2).map(|w| w[1] - w[0]).collect();
+        }
+
+        // Horner on the Newton form, multiplying by (x - j) from the innermost term out
+        let mut coefficients = vec![newton[k]];
+        for j in (0..k).rev() {
+            let node = F::from_u64(j as u64);
+
+            coefficients.insert(0, F::zero());
+            for t in 0..coefficients.len() - 1 {
+                coefficients[t] = coefficients[t] - coefficients[t + 1] * node;
+            }
+            coefficients[0] = coefficients[0] + newton[j];
+        }
+
+        UPolynomial::from(coefficients)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fp::Fp;
+    use crate::interpolation::InterpolationDomain;
+    use crate::upolynomial::UPolynomial;
+
+    #[test]
+    fn test_weights() {
+        let domain = InterpolationDomain::<Fp>::new(3);
+
+        // 1 / prod_{j != i} (i - j) over {0, 1, 2, 3}
+        let expected = [-Fp(6), Fp(2), -Fp(2), Fp(6)].map(|d| Fp(1) / d);
+        assert_eq!(domain.weights(3), expected.to_vec());
+        assert_eq!(domain.weights(0), vec![Fp(1)]);
+    }
+
+    #[test]
+    fn test_evaluate() {
+        // 2x^3 + 5x + 7
+        let f = |x: u64| Fp(2 * x * x * x + 5 * x + 7);
+        let evaluations: Vec<Fp> = (0..4).map(f).collect();
+        let domain = InterpolationDomain::new(5);
+
+        for x in [0, 3, 4, 10, 1000] {
+            assert_eq!(domain.evaluate(&evaluations, Fp(x)), f(x));
+        }
+    }
+
+    #[test]
+    fn test_interpolate_matches_lagrange() {
+        let domain = InterpolationDomain::new(8);
+
+        for degree in 0..=8 {
+            let evaluations: Vec<Fp> = (0..=degree).map(|_| Fp::sample()).collect();
+            let points = evaluations
+                .iter()
+                .enumerate()
+                .map(|(x, y)| (Fp(x as u64), *y))
+                .collect();
+
+            let polynomial = domain.interpolate(&evaluations);
+            assert_eq!(
+                polynomial.coefficients,
+                UPolynomial::interpolate(points).unwrap().coefficients
+            );
+
+            let x = Fp::sample();
+            assert_eq!(polynomial.evaluate(x), domain.evaluate(&evaluations, x));
+        }
+    }
+
+    #[test]
+    fn test_interpolate_keeps_zero_top_coefficients() {
+        // A line given by three evaluations
+        let polynomial = InterpolationDomain::new(2).interpolate(&[Fp(1), Fp(3), Fp(5)]);
+
+        assert_eq!(polynomial.coefficients, vec![Fp(1), Fp(2), Fp(0)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "domain is too small")]
+    fn test_domain_too_small() {
+        InterpolationDomain::new(1).interpolate(&[Fp(1), Fp(2), Fp(3)]);
+    }
+}
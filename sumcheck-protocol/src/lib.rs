@@ -1,93 +1,127 @@
 extern crate core;
 
+use crate::error::SumcheckError;
+use crate::field::Field;
 use crate::fp::Fp;
 use crate::mpolynomial::MPolynomial;
+use crate::prover::{Prover, SumcheckProver};
 use crate::upolynomial::UPolynomial;
+use crate::verifier::SumcheckVerifier;
 
+pub mod babybear;
+pub mod batch;
+pub mod error;
+pub mod field;
 pub mod fp;
+pub mod fp2;
+pub mod fp3;
+pub mod fri;
+pub mod gkr;
+pub mod interpolation;
+pub mod ligero;
+pub mod merkle;
+pub mod mle;
 pub mod mpolynomial;
+pub mod ntt;
+pub mod pcs;
+pub mod proof;
+pub mod prover;
+pub mod round_message;
+pub mod transcript;
 pub mod upolynomial;
+pub mod verifier;
+pub mod virtual_polynomial;
+pub mod zerocheck;
 
-pub struct SumcheckProtocol {
-    polynomial: MPolynomial,
-    interaction_completed: bool,
-    randomness: Vec<Fp>,
-    statement: Fp,
-    step: usize,
-    previous_step: Option<UPolynomial>
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VerifierState<F: Field = Fp> {
+    /// The round was accepted, the challenge has to be sent to the prover.
+    Challenge(F),
+    /// All rounds and the final evaluation were accepted.
+    Accepted,
 }
 
-impl SumcheckProtocol {
-    pub fn new(polynomial: MPolynomial) -> Self {
-        Self {
-            polynomial,
-            interaction_completed: false,
-            randomness: vec![],
-            statement: Fp(0),
-            step: 0,
-            previous_step: None
-        }
-    }
+/// Runs an honest `Prover`, by default a `SumcheckProver`, against a `SumcheckVerifier` in-process.
+pub struct SumcheckProtocol<F: Field = Fp, P: Prover<F> = SumcheckProver<F>> {
+    prover: P,
+    verifier: SumcheckVerifier<'static, F>,
+    challenge: Option<F>,
+}
 
-    pub fn prove(&mut self) -> Option<UPolynomial> {
-        if self.step > self.polynomial.number_of_vars() || self.interaction_completed {
-            return None;
-        }
+impl<F: Field> SumcheckProtocol<F> {
+    pub fn new(polynomial: MPolynomial<F>) -> Self {
+        let claim = polynomial.sum_over_hyper_cube(None);
+        let oracle = polynomial.clone();
 
-        if self.statement == Fp(0) {
-            self.statement = self.polynomial.sum_over_hyper_cube(None);
-        }
+        Self::with_claim(polynomial, claim, move |x| oracle.eval(x))
+    }
 
-        Some(self.polynomial.fix_var_over_hyper_cube(Some(&self.randomness)))
+    /// Checks `claim` instead of the honest sum, the oracle can evaluate the
+    /// polynomial in a cheaper form than its expanded terms.
+    pub fn with_claim(
+        polynomial: MPolynomial<F>,
+        claim: F,
+        oracle: impl Fn(&[F]) -> F + 'static,
+    ) -> Self {
+        Self::from_prover(SumcheckProver::new(polynomial), claim, oracle)
     }
+}
 
-    pub fn verify(&mut self, rec_polynomial: Option<UPolynomial>) {
-        assert!(rec_polynomial.is_some());
+impl<F: Field, P: Prover<F>> SumcheckProtocol<F, P> {
+    /// Same as `with_claim` for any prover, the verifier takes the number of
+    /// variables and the degree bound from it.
+    pub fn from_prover(prover: P, claim: F, oracle: impl Fn(&[F]) -> F + 'static) -> Self {
+        let verifier = SumcheckVerifier::new(
+            claim,
+            prover.number_of_vars(),
+            prover.degree(),
+            oracle,
+        );
 
-        if self.interaction_completed {
-            return;
+        Self {
+            prover,
+            verifier,
+            challenge: None,
         }
+    }
 
-        assert!(rec_polynomial.clone().unwrap().degree() <= self.polynomial.degree_ind());
-
-        // FIXME: consider bigger degrees
-        let res1 = rec_polynomial.clone().unwrap().eval(&*vec![Fp(0)]);
-        let res2 = rec_polynomial.clone().unwrap().eval(&*vec![Fp(1)]);
-
-        if self.previous_step.is_none() {
-            self.previous_step = rec_polynomial.clone();
-            assert_eq!(res1 + res2, self.statement);
-        } else {
-            assert!(self.randomness.len() > 0);
-            let latest_randomness = self.randomness.get(self.randomness.len() - 1).unwrap();
-            assert_eq!(res1 + res2, self.previous_step.clone().unwrap().eval(&*vec![*latest_randomness]));
-            self.previous_step = rec_polynomial.clone();
+    pub fn prove(&mut self) -> Option<UPolynomial<F>> {
+        if self.verifier.is_accepted() {
+            return None;
         }
 
-        if self.randomness.len() == self.polynomial.number_of_vars() - 1 {
-            self.randomness.push(Fp::sample());
-            let latest_randomness = self.randomness.get(self.randomness.len() - 1).unwrap();
-            assert_eq!(self.polynomial.eval(&*self.randomness), rec_polynomial.clone().unwrap().eval(&*vec![*latest_randomness]));
-            self.interaction_completed = true;
+        self.prover.round(self.challenge.take())
+    }
 
-            return;
-        }
+    pub fn verify(&mut self, rec_polynomial: Option<UPolynomial<F>>) -> Result<VerifierState<F>, SumcheckError> {
+        let state = self.verifier.receive(rec_polynomial)?;
 
-        self.step += 1;
+        if let VerifierState::Challenge(challenge) = state {
+            self.challenge = Some(challenge);
+        }
 
-        self.randomness.push(Fp::sample())
+        Ok(state)
     }
 
     pub fn is_verifier_accept(&self) -> bool {
-        self.interaction_completed
+        self.verifier.is_accepted()
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::error::SumcheckError;
     use crate::fp::Fp;
     use crate::mpolynomial::MPolynomial;
-    use crate::SumcheckProtocol;
+    use crate::upolynomial::UPolynomial;
+    use crate::{SumcheckProtocol, VerifierState};
+
+    fn protocol() -> SumcheckProtocol {
+        let coefficients = vec![Fp(2), Fp(3), Fp(5), Fp(7)];
+        let powers = vec![1, 1, 1];
+
+        SumcheckProtocol::new(MPolynomial::from(coefficients, powers))
+    }
 
     #[test]
     fn test_sumcheck_protocol() {
@@ -99,14 +133,130 @@ mod tests {
         let mut protocol = SumcheckProtocol::new(polynomial);
 
         let step = protocol.prove();
-        protocol.verify(step);
+        assert!(matches!(protocol.verify(step), Ok(VerifierState::Challenge(_))));
 
         let step = protocol.prove();
-        protocol.verify(step);
+        assert!(matches!(protocol.verify(step), Ok(VerifierState::Challenge(_))));
 
         let step = protocol.prove();
-        protocol.verify(step);
+        assert_eq!(protocol.verify(step), Ok(VerifierState::Accepted));
 
         assert!(protocol.is_verifier_accept())
     }
+
+    #[test]
+    fn test_sumcheck_protocol_higher_degrees() {
+        let coefficients = vec![Fp(2), Fp(3), Fp(5), Fp(7)];
+        let powers = vec![3, 2, 5];
+
+        let mut protocol = SumcheckProtocol::new(MPolynomial::from(coefficients, powers));
+
+        while let Some(step) = protocol.prove() {
+            assert!(protocol.verify(Some(step)).is_ok());
+        }
+
+        assert!(protocol.is_verifier_accept())
+    }
+
+    #[test]
+    fn test_linear_round_polynomial_rejected_for_higher_degrees() {
+        // g(x) = x^3 + 3 on the first round, a line through g(0) and g(1) passes the
+        // sum check but not the final evaluation
+        let polynomial = MPolynomial::from(vec![Fp(1), Fp(3)], vec![3]);
+        let mut protocol = SumcheckProtocol::new(polynomial);
+
+        let line = UPolynomial::from_coeffs_be(vec![Fp(1), Fp(3)]);
+        assert_eq!(protocol.verify(Some(line)), Err(SumcheckError::FinalEvaluationMismatch));
+    }
+
+    #[test]
+    fn test_missing_message() {
+        let mut protocol = protocol();
+
+        assert_eq!(protocol.verify(None), Err(SumcheckError::MissingMessage));
+    }
+
+    #[test]
+    fn test_degree_too_high() {
+        let mut protocol = protocol();
+        protocol.prove();
+
+        let malicious = UPolynomial::from_coeffs_be(vec![Fp(1), Fp(0), Fp(48)]);
+
+        assert_eq!(
+            protocol.verify(Some(malicious)),
+            Err(SumcheckError::DegreeTooHigh { round: 0, degree: 2, max_degree: 1 })
+        );
+    }
+
+    #[test]
+    fn test_round_sum_mismatch() {
+        let mut protocol = protocol();
+        protocol.prove();
+
+        // 2x + 47 sums to 96 over {0, 1} but is not the honest first round polynomial,
+        // it still passes, the lie is only caught one round later
+        let malicious = UPolynomial::from_coeffs_be(vec![Fp(2), Fp(47)]);
+        assert!(protocol.verify(Some(malicious)).is_ok());
+
+        protocol.prove();
+        let malicious = UPolynomial::from_coeffs_be(vec![Fp(0), Fp(0)]);
+        assert_eq!(
+            protocol.verify(Some(malicious)),
+            Err(SumcheckError::RoundSumMismatch { round: 1 })
+        );
+    }
+
+    #[test]
+    fn test_first_round_sum_mismatch() {
+        let mut protocol = protocol();
+        let honest = protocol.prove().unwrap();
+
+        let mut malicious = honest.clone();
+        malicious.coefficients[1] = malicious.coefficients[1] + Fp(1);
+
+        assert_eq!(
+            protocol.verify(Some(malicious)),
+            Err(SumcheckError::RoundSumMismatch { round: 0 })
+        );
+
+        // A rejected message does not advance the protocol
+        assert!(matches!(protocol.verify(Some(honest)), Ok(VerifierState::Challenge(_))));
+    }
+
+    #[test]
+    fn test_final_evaluation_mismatch() {
+        let mut protocol = protocol();
+
+        for _ in 0..2 {
+            let step = protocol.prove();
+            assert!(protocol.verify(step).is_ok());
+        }
+
+        // Same sum over {0, 1} as the honest polynomial, but a different line
+        let honest = protocol.prove().unwrap();
+        let malicious = UPolynomial::from(vec![
+            honest.coefficients[0] - Fp(1),
+            honest.coefficients[1] + Fp(2),
+        ]);
+
+        assert_eq!(
+            protocol.verify(Some(malicious)),
+            Err(SumcheckError::FinalEvaluationMismatch)
+        );
+        assert!(!protocol.is_verifier_accept());
+    }
+
+    #[test]
+    fn test_protocol_finished() {
+        let mut protocol = protocol();
+
+        for _ in 0..3 {
+            let step = protocol.prove();
+            assert!(protocol.verify(step).is_ok());
+        }
+
+        let step = UPolynomial::from_coeffs_be(vec![Fp(0), Fp(0)]);
+        assert_eq!(protocol.verify(Some(step)), Err(SumcheckError::ProtocolFinished));
+    }
 }
\ No newline at end of file
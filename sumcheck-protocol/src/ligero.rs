@@ -0,0 +1,369 @@
+use crate::field::Field;
+use crate::fp::Fp;
+use crate::merkle::{Hash, MerkleTree};
+use crate::mle::{eq_table, DenseMultilinearExtension};
+use crate::pcs::PolynomialCommitmentScheme;
+use crate::transcript::Transcript;
+
+/// Hash-based multilinear commitment in the style of Ligero and Brakedown.
+///
+/// The evaluation table is laid out as a matrix whose columns are indexed by the
+/// first half of the variables and whose rows by the rest, so that
+/// `f(r) = eq(r_rows)^T * M * eq(r_columns)`. Every row is Reed-Solomon encoded
+/// with rate `1 / blowup` and the columns of the encoded matrix are committed in
+/// a Merkle tree.
+///
+/// An opening sends two combinations of the rows, a random one that tests that
+/// the committed rows are close to codewords and the `eq(r_rows)` one that gives
+/// the value, and checks both against randomly queried columns. More queries
+/// lower the soundness error, at the cost of larger openings.
+#[derive(Debug, Clone, Copy)]
+pub struct Ligero {
+    blowup: usize,
+    number_of_queries: usize,
+}
+
+/// Row combinations and queried columns of a Ligero opening.
+#[derive(Debug, Clone)]
+pub struct LigeroOpening<F: Field = Fp> {
+    pub test_row: Vec<F>,
+    pub evaluation_row: Vec<F>,
+    pub columns: Vec<Vec<F>>,
+    pub paths: Vec<Vec<Hash>>,
+}
+
+/// Encoded rows and the Merkle tree over their columns, kept by the prover between
+/// `commit` and `open`.
+#[derive(Debug, Clone)]
+pub struct LigeroProverData<F: Field = Fp> {
+    encoded: Vec<Vec<F>>,
+    tree: MerkleTree,
+}
+
+impl Default for Ligero {
+    fn default() -> Self {
+        Self::new(4, 32)
+    }
+}
+
+impl Ligero {
+    pub fn new(blowup: usize, number_of_queries: usize) -> Self {
+        assert!(
+            blowup >= 2 && blowup.is_power_of_two(),
+            "blowup must be a power of two of at least 2"
+        );
+        assert!(number_of_queries > 0, "at least one query is needed");
+
+        Self {
+            blowup,
+            number_of_queries,
+        }
+    }
+
+    /// Number of column and row variables of the matrix for `number_of_vars` variables.
+    fn dimensions(number_of_vars: usize) -> (usize, usize) {
+        let column_vars = number_of_vars - number_of_vars / 2;
+
+        (column_vars, number_of_vars - column_vars)
+    }
+
+    /// Evaluates the polynomial with coefficients `message` at `0, 1, ..., blowup * len - 1`.
+    ///
+    /// Horner at every point is quadratic in `message.len()`, but works over any field
+    /// while an NTT would need a two-adic domain of `F`.
+    fn encode<F: Field>(&self, message: &[F]) -> Vec<F> {
+        (0..(self.blowup * message.len()) as u64)
+            .map(|x| {
+                let x = F::from_u64(x);
+
+                message
+                    .iter()
+                    .rev()
+                    .fold(F::zero(), |acc, coefficient| acc * x + *coefficient)
+            })
+            .collect()
+    }
+
+    fn hash_column<F: Field>(column: &[F]) -> Hash {
+        let bytes: Vec<u8> = column.iter().flat_map(|value| value.to_bytes()).collect();
+
+        MerkleTree::hash_leaf(&bytes)
+    }
+
+    /// Encoded rows and the Merkle tree over the encoded columns.
+    fn encode_matrix<F: Field>(
+        &self,
+        polynomial: &DenseMultilinearExtension<F>,
+    ) -> LigeroProverData<F> {
+        let (column_vars, _) = Self::dimensions(polynomial.number_of_vars());
+
+        let rows: Vec<Vec<F>> = polynomial
+            .evaluations
+            .chunks(1 << column_vars)
+            .map(|row| self.encode(row))
+            .collect();
+
+        let leaves = (0..rows[0].len())
+            .map(|j| {
+                let column: Vec<F> = rows.iter().map(|row| row[j]).collect();
+                Self::hash_column(&column)
+            })
+            .collect();
+
+        LigeroProverData {
+            encoded: rows,
+            tree: MerkleTree::from(leaves),
+        }
+    }
+
+    fn combine_rows<F: Field>(weights: &[F], rows: &[Vec<F>]) -> Vec<F> {
+        let mut combined = vec![F::zero(); rows[0].len()];
+
+        for (weight, row) in weights.iter().zip(rows) {
+            for (acc, value) in combined.iter_mut().zip(row) {
+                *acc = *acc + *weight * *value;
+            }
+        }
+
+        combined
+    }
+
+    fn dot<F: Field>(x: &[F], y: &[F]) -> F {
+        x.iter().zip(y).fold(F::zero(), |acc, (a, b)| acc + *a * *b)
+    }
+}
+
+fn absorb_commitment<F: Field>(transcript: &mut Transcript, root: &Hash, point: &[F]) {
+    transcript.absorb_bytes(b"ligero_root", root);
+    transcript.absorb_fields(b"ligero_point", point);
+}
+
+impl<F: Field> PolynomialCommitmentScheme<F> for Ligero {
+    type Commitment = Hash;
+    type Opening = LigeroOpening<F>;
+    type ProverData = LigeroProverData<F>;
+
+    fn commit(
+        &self,
+        polynomial: &DenseMultilinearExtension<F>,
+    ) -> (Self::Commitment, Self::ProverData) {
+        let data = self.encode_matrix(polynomial);
+
+        (data.tree.root(), data)
+    }
+
+    fn open(
+        &self,
+        polynomial: &DenseMultilinearExtension<F>,
+        data: &Self::ProverData,
+        point: &[F],
+        transcript: &mut Transcript,
+    ) -> (F, Self::Opening) {
+        assert_eq!(point.len(), polynomial.number_of_vars());
+
+        let (column_vars, _) = Self::dimensions(point.len());
+        let LigeroProverData { encoded, tree } = data;
+        let rows: Vec<Vec<F>> = polynomial
+            .evaluations
+            .chunks(1 << column_vars)
+            .map(|row| row.to_vec())
+            .collect();
+
+        absorb_commitment(transcript, &tree.root(), point);
+
+        let weights: Vec<F> = (0..rows.len())
+            .map(|_| transcript.challenge(b"ligero_test"))
+            .collect();
+        let test_row = Self::combine_rows(&weights, &rows);
+        transcript.absorb_fields(b"ligero_test_row", &test_row);
+
+        let evaluation_row = Self::combine_rows(&eq_table(&point[column_vars..]), &rows);
+        transcript.absorb_fields(b"ligero_evaluation_row", &evaluation_row);
+
+        let value = Self::dot(&evaluation_row, &eq_table(&point[..column_vars]));
+
+        let (columns, paths) = (0..self.number_of_queries)
+            .map(|_| {
+                let index = transcript.challenge_index(b"ligero_query", tree.number_of_leaves());
+                let column = encoded.iter().map(|row| row[index]).collect();
+
+                (column, tree.open(index))
+            })
+            .unzip();
+
+        (
+            value,
+            LigeroOpening {
+                test_row,
+                evaluation_row,
+                columns,
+                paths,
+            },
+        )
+    }
+
+    fn verify_opening(
+        &self,
+        commitment: &Self::Commitment,
+        point: &[F],
+        value: F,
+        opening: &Self::Opening,
+        transcript: &mut Transcript,
+    ) -> bool {
+        let (column_vars, row_vars) = Self::dimensions(point.len());
+
+        if opening.test_row.len() != 1 << column_vars
+            || opening.evaluation_row.len() != 1 << column_vars
+            || opening.columns.len() != self.number_of_queries
+            || opening.paths.len() != self.number_of_queries
+            || opening.columns.iter().any(|c| c.len() != 1 << row_vars)
+        {
+            return false;
+        }
+
+        absorb_commitment(transcript, commitment, point);
+
+        let weights: Vec<F> = (0..1 << row_vars)
+            .map(|_| transcript.challenge(b"ligero_test"))
+            .collect();
+        transcript.absorb_fields(b"ligero_test_row", &opening.test_row);
+        transcript.absorb_fields(b"ligero_evaluation_row", &opening.evaluation_row);
+
+        if Self::dot(&opening.evaluation_row, &eq_table(&point[..column_vars])) != value {
+            return false;
+        }
+
+        let encoded_test = self.encode(&opening.test_row);
+        let encoded_evaluation = self.encode(&opening.evaluation_row);
+        let eq_rows = eq_table(&point[column_vars..]);
+
+        opening
+            .columns
+            .iter()
+            .zip(&opening.paths)
+            .all(|(column, path)| {
+                let index = transcript.challenge_index(b"ligero_query", encoded_test.len());
+
+                MerkleTree::verify(commitment, index, &Self::hash_column(column), path)
+                    && Self::dot(&weights, column) == encoded_test[index]
+                    && Self::dot(&eq_rows, column) == encoded_evaluation[index]
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::SumcheckError;
+    use crate::fp::Fp;
+    use crate::ligero::{Ligero, LigeroOpening};
+    use crate::mle::DenseMultilinearExtension;
+    use crate::pcs::{prove_committed, verify_committed, PolynomialCommitmentScheme};
+    use crate::transcript::Transcript;
+
+    fn table(number_of_vars: usize) -> DenseMultilinearExtension {
+        DenseMultilinearExtension::from(
+            (0..1u64 << number_of_vars)
+                .map(|i| Fp(i * i + 3 * i + 1))
+                .collect(),
+        )
+    }
+
+    fn verify(
+        ligero: &Ligero,
+        commitment: &[u8; 32],
+        point: &[Fp],
+        value: Fp,
+        opening: &LigeroOpening,
+    ) -> bool {
+        ligero.verify_opening(
+            commitment,
+            point,
+            value,
+            opening,
+            &mut Transcript::new(b"test"),
+        )
+    }
+
+    #[test]
+    fn test_opening() {
+        let ligero = Ligero::default();
+
+        for number_of_vars in 0..8 {
+            let table = table(number_of_vars);
+            let (commitment, data) = ligero.commit(&table);
+            let point: Vec<Fp> = (0..number_of_vars).map(|_| Fp::sample()).collect();
+
+            let (value, opening) =
+                ligero.open(&table, &data, &point, &mut Transcript::new(b"test"));
+
+            assert_eq!(value, table.eval(&point));
+            assert!(verify(&ligero, &commitment, &point, value, &opening));
+            assert!(!verify(
+                &ligero,
+                &commitment,
+                &point,
+                value + Fp(1),
+                &opening
+            ));
+        }
+    }
+
+    #[test]
+    fn test_tampered_opening_is_rejected() {
+        let ligero = Ligero::new(2, 16);
+        let table = table(6);
+        let (commitment, data) = ligero.commit(&table);
+        let point: Vec<Fp> = (0..6).map(|_| Fp::sample()).collect();
+
+        let (value, opening) = ligero.open(&table, &data, &point, &mut Transcript::new(b"test"));
+
+        let mut tampered = opening.clone();
+        tampered.columns[0][1] = tampered.columns[0][1] + Fp(1);
+        assert!(!verify(&ligero, &commitment, &point, value, &tampered));
+
+        let mut tampered = opening.clone();
+        tampered.test_row[2] = tampered.test_row[2] + Fp(1);
+        assert!(!verify(&ligero, &commitment, &point, value, &tampered));
+
+        let mut tampered = opening.clone();
+        tampered.paths[0][0][0] ^= 1;
+        assert!(!verify(&ligero, &commitment, &point, value, &tampered));
+
+        let mut tampered = opening;
+        tampered.columns.pop();
+        assert!(!verify(&ligero, &commitment, &point, value, &tampered));
+    }
+
+    #[test]
+    fn test_opening_of_other_polynomial_is_rejected() {
+        let ligero = Ligero::default();
+        let table = table(5);
+        let (commitment, data) = ligero.commit(&table);
+        let point: Vec<Fp> = (0..5).map(|_| Fp::sample()).collect();
+
+        let mut other = table.clone();
+        other.evaluations[9] = other.evaluations[9] + Fp(1);
+
+        let (value, opening) = ligero.open(&other, &data, &point, &mut Transcript::new(b"test"));
+        assert!(!verify(&ligero, &commitment, &point, value, &opening));
+    }
+
+    #[test]
+    fn test_committed_sumcheck() {
+        let ligero = Ligero::default();
+        let table = table(7);
+        let claim = table.sum_over_hyper_cube();
+        let (commitment, data) = ligero.commit(&table);
+
+        let proof = prove_committed(&ligero, &table, &commitment, &data);
+        let randomness = verify_committed(&ligero, &commitment, 7, claim, &proof).unwrap();
+        assert_eq!(proof.proof.final_evaluation, table.eval(&randomness));
+
+        let mut proof = proof;
+        proof.opening.evaluation_row[0] = proof.opening.evaluation_row[0] + Fp(1);
+        assert_eq!(
+            verify_committed(&ligero, &commitment, 7, claim, &proof).err(),
+            Some(SumcheckError::InvalidOpening)
+        );
+    }
+}
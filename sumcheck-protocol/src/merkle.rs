@@ -0,0 +1,170 @@
+use sha2::{Digest, Sha256};
+
+pub type Hash = [u8; 32];
+
+/// Binary SHA-256 Merkle tree over a power-of-two number of leaves.
+///
+/// Leaves and inner nodes are hashed with different prefixes, so a node can
+/// never be passed off as a leaf.
+#[derive(Debug, Clone)]
+pub struct MerkleTree {
+    /// `layers[0]` are the leaf hashes, the last layer is the root.
+    layers: Vec<Vec<Hash>>,
+}
+
+impl MerkleTree {
+    pub fn from(leaves: Vec<Hash>) -> Self {
+        assert!(
+            leaves.len().is_power_of_two(),
+            "number of leaves must be a power of two"
+        );
+
+        let mut layers = vec![leaves];
+
+        while layers.last().unwrap().len() > 1 {
+            let layer = layers
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|pair| Self::hash_node(&pair[0], &pair[1]))
+                .collect();
+
+            layers.push(layer);
+        }
+
+        Self { layers }
+    }
+
+    pub fn hash_leaf(data: &[u8]) -> Hash {
+        let mut hasher = Sha256::new();
+
+        hasher.update([0]);
+        hasher.update(data);
+
+        hasher.finalize().into()
+    }
+
+    fn hash_node(left: &Hash, right: &Hash) -> Hash {
+        let mut hasher = Sha256::new();
+
+        hasher.update([1]);
+        hasher.update(left);
+        hasher.update(right);
+
+        hasher.finalize().into()
+    }
+
+    pub fn root(&self) -> Hash {
+        self.layers.last().unwrap()[0]
+    }
+
+    pub fn number_of_leaves(&self) -> usize {
+        self.layers[0].len()
+    }
+
+    /// Sibling hashes from the leaf up to the root.
+    pub fn open(&self, index: usize) -> Vec<Hash> {
+        assert!(index < self.number_of_leaves());
+
+        let mut path = vec![];
+        let mut index = index;
+
+        for layer in &self.layers[..self.layers.len() - 1] {
+            path.push(layer[index ^ 1]);
+            index >>= 1;
+        }
+
+        path
+    }
+
+    pub fn verify(root: &Hash, index: usize, leaf: &Hash, path: &[Hash]) -> bool {
+        // A path of 64 or more hashes from an untrusted proof must not overflow the shift
+        if index
+            .checked_shr(path.len() as u32)
+            .is_some_and(|rest| rest != 0)
+        {
+            return false;
+        }
+
+        let mut node = *leaf;
+        let mut index = index;
+
+        for sibling in path {
+            node = if index & 1 == 0 {
+                Self::hash_node(&node, sibling)
+            } else {
+                Self::hash_node(sibling, &node)
+            };
+            index >>= 1;
+        }
+
+        node == *root
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::merkle::MerkleTree;
+
+    fn leaves(n: u8) -> Vec<[u8; 32]> {
+        (0..n).map(|i| MerkleTree::hash_leaf(&[i])).collect()
+    }
+
+    #[test]
+    fn test_open_and_verify() {
+        let tree = MerkleTree::from(leaves(8));
+        let root = tree.root();
+
+        for index in 0..8 {
+            let path = tree.open(index);
+
+            assert_eq!(path.len(), 3);
+            assert!(MerkleTree::verify(&root, index, &leaves(8)[index], &path));
+            assert!(!MerkleTree::verify(
+                &root,
+                index ^ 1,
+                &leaves(8)[index],
+                &path
+            ));
+            assert!(!MerkleTree::verify(
+                &root,
+                index + 8,
+                &leaves(8)[index],
+                &path
+            ));
+        }
+    }
+
+    #[test]
+    fn test_single_leaf() {
+        let tree = MerkleTree::from(leaves(1));
+
+        assert_eq!(tree.root(), leaves(1)[0]);
+        assert!(tree.open(0).is_empty());
+        assert!(MerkleTree::verify(&tree.root(), 0, &leaves(1)[0], &[]));
+    }
+
+    #[test]
+    fn test_tampered_path() {
+        let tree = MerkleTree::from(leaves(4));
+        let mut path = tree.open(2);
+        path[1][0] ^= 1;
+
+        assert!(!MerkleTree::verify(&tree.root(), 2, &leaves(4)[2], &path));
+    }
+
+    #[test]
+    fn test_oversized_path() {
+        let tree = MerkleTree::from(leaves(4));
+        let mut path = tree.open(2);
+        path.resize(70, [0; 32]);
+
+        assert!(!MerkleTree::verify(&tree.root(), 2, &leaves(4)[2], &path));
+        assert!(!MerkleTree::verify(
+            &tree.root(),
+            usize::MAX,
+            &leaves(4)[2],
+            &path
+        ));
+    }
+}
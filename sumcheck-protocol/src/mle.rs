@@ -0,0 +1,203 @@
+use crate::field::{ExtensionField, Field};
+use crate::fp::Fp;
+
+/// Multilinear polynomial given by its evaluations over the boolean hypercube.
+///
+/// Bit `j` of an index is the value of variable `x_{j+1}`, so the first variable
+/// splits the table into even and odd entries.
+#[derive(Debug, Clone)]
+pub struct DenseMultilinearExtension<F: Field = Fp> {
+    pub evaluations: Vec<F>,
+    number_of_vars: usize,
+}
+
+impl<F: Field> DenseMultilinearExtension<F> {
+    pub fn from(evaluations: Vec<F>) -> Self {
+        assert!(
+            evaluations.len().is_power_of_two(),
+            "number of evaluations must be a power of two"
+        );
+
+        let number_of_vars = evaluations.len().trailing_zeros() as usize;
+
+        Self {
+            evaluations,
+            number_of_vars,
+        }
+    }
+
+    pub fn number_of_vars(&self) -> usize {
+        self.number_of_vars
+    }
+
+    pub fn sum_over_hyper_cube(&self) -> F {
+        self.evaluations.iter().fold(F::zero(), |acc, x| acc + *x)
+    }
+
+    /// Binds the first variable to `r` in place, halving the table.
+    pub fn fix_first_variable(&mut self, r: F) {
+        assert!(self.number_of_vars > 0, "no variables left to fix");
+
+        let half = self.evaluations.len() / 2;
+
+        for i in 0..half {
+            let low = self.evaluations[2 * i];
+            let high = self.evaluations[2 * i + 1];
+
+            self.evaluations[i] = low + r * (high - low);
+        }
+
+        self.evaluations.truncate(half);
+        self.number_of_vars -= 1;
+    }
+
+    /// Binds the first variable to an extension field element, the base table is left untouched.
+    ///
+    /// Costs one base field subtraction and one mixed multiplication per pair,
+    /// instead of lifting the whole table first.
+    pub fn fix_first_variable_into<E: ExtensionField<F>>(
+        &self,
+        r: E,
+    ) -> DenseMultilinearExtension<E> {
+        assert!(self.number_of_vars > 0, "no variables left to fix");
+
+        let evaluations = self
+            .evaluations
+            .chunks(2)
+            .map(|pair| E::from_base(pair[0]) + r * (pair[1] - pair[0]))
+            .collect();
+
+        DenseMultilinearExtension {
+            evaluations,
+            number_of_vars: self.number_of_vars - 1,
+        }
+    }
+
+    pub fn lift<E: ExtensionField<F>>(&self) -> DenseMultilinearExtension<E> {
+        DenseMultilinearExtension {
+            evaluations: self.evaluations.iter().map(|x| E::from_base(*x)).collect(),
+            number_of_vars: self.number_of_vars,
+        }
+    }
+
+    pub fn eval(&self, x: &[F]) -> F {
+        assert_eq!(x.len(), self.number_of_vars);
+
+        let mut folded = self.clone();
+        for r in x {
+            folded.fix_first_variable(*r);
+        }
+
+        folded.evaluations[0]
+    }
+}
+
+/// Evaluations of `eq(point, x) = prod_j (point_j * x_j + (1 - point_j) * (1 - x_j))`
+/// over the hypercube, in the same index order as `DenseMultilinearExtension`.
+pub fn eq_table<F: Field>(point: &[F]) -> Vec<F> {
+    let mut table = vec![F::one()];
+
+    for r in point {
+        let len = table.len();
+        table.resize(2 * len, F::zero());
+
+        for i in 0..len {
+            let high = table[i] * *r;
+
+            table[i + len] = high;
+            table[i] -= high;
+        }
+    }
+
+    table
+}
+
+/// `eq(x, y)` at arbitrary points, one when `x == y` on the hypercube and zero elsewhere on it.
+pub fn eq_eval<F: Field>(x: &[F], y: &[F]) -> F {
+    assert_eq!(x.len(), y.len());
+
+    x.iter().zip(y).fold(F::one(), |acc, (a, b)| {
+        acc * (*a * *b + (F::one() - *a) * (F::one() - *b))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fp::Fp;
+    use crate::fp2::Fp2;
+    use crate::mle::{eq_eval, eq_table, DenseMultilinearExtension};
+    use crate::mpolynomial::MPolynomial;
+
+    #[test]
+    fn test_eval_on_hyper_cube() {
+        let mle = DenseMultilinearExtension::from(vec![Fp(1), Fp(2), Fp(3), Fp(4)]);
+
+        assert_eq!(mle.number_of_vars(), 2);
+        assert_eq!(mle.eval(&[Fp(0), Fp(0)]), Fp(1));
+        assert_eq!(mle.eval(&[Fp(1), Fp(0)]), Fp(2));
+        assert_eq!(mle.eval(&[Fp(0), Fp(1)]), Fp(3));
+        assert_eq!(mle.eval(&[Fp(1), Fp(1)]), Fp(4));
+        assert_eq!(mle.sum_over_hyper_cube(), Fp(10));
+    }
+
+    #[test]
+    fn test_eval_matches_mpolynomial() {
+        // 2 * x1 + 3 * x2 + 5 * x3 + 7
+        let polynomial = MPolynomial::from(vec![Fp(2), Fp(3), Fp(5), Fp(7)], vec![1, 1, 1]);
+
+        let evaluations = (0..8u64)
+            .map(|i| polynomial.eval(&[Fp(i & 1), Fp((i >> 1) & 1), Fp((i >> 2) & 1)]))
+            .collect();
+        let mle = DenseMultilinearExtension::from(evaluations);
+
+        let x = [Fp(11), Fp(13), Fp(17)];
+        assert_eq!(mle.eval(&x), polynomial.eval(&x));
+    }
+
+    #[test]
+    fn test_fix_first_variable_into_extension() {
+        let mle = DenseMultilinearExtension::from(vec![Fp(1), Fp(2), Fp(3), Fp(4)]);
+        let x = [Fp2(Fp(5), Fp(9)), Fp2(Fp(6), Fp(10))];
+
+        let folded = mle.fix_first_variable_into(x[0]);
+
+        assert_eq!(folded.number_of_vars(), 1);
+        assert_eq!(folded.eval(&x[1..]), mle.lift::<Fp2>().eval(&x));
+    }
+
+    #[test]
+    fn test_fix_first_variable() {
+        let mut mle = DenseMultilinearExtension::from(vec![Fp(1), Fp(2), Fp(3), Fp(4)]);
+        let expected = mle.eval(&[Fp(5), Fp(6)]);
+
+        mle.fix_first_variable(Fp(5));
+
+        assert_eq!(mle.number_of_vars(), 1);
+        assert_eq!(mle.evaluations, vec![Fp(6), Fp(8)]);
+        assert_eq!(mle.eval(&[Fp(6)]), expected);
+    }
+
+    #[test]
+    fn test_eq_table() {
+        let point = [Fp(3), Fp(5), Fp(7)];
+        let table = eq_table(&point);
+
+        for (i, value) in table.iter().enumerate() {
+            let x = [
+                Fp(i as u64 & 1),
+                Fp((i as u64 >> 1) & 1),
+                Fp((i as u64 >> 2) & 1),
+            ];
+            assert_eq!(*value, eq_eval(&point, &x));
+        }
+
+        // eq(point, .) interpolates the point, so summing it against a table evaluates the table
+        let mle = DenseMultilinearExtension::from((0..8).map(|i| Fp(i * i + 1)).collect());
+        let dot = table
+            .iter()
+            .zip(&mle.evaluations)
+            .fold(Fp(0), |acc, (a, b)| acc + *a * *b);
+
+        assert_eq!(dot, mle.eval(&point));
+    }
+}
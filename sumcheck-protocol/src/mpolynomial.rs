@@ -1,94 +1,297 @@
+use crate::field::{ExtensionField, Field};
 use crate::fp::Fp;
+use crate::interpolation::InterpolationDomain;
 use crate::upolynomial::UPolynomial;
+use std::collections::BTreeMap;
+use std::ops::{Add, Mul, Neg, Sub};
 
-pub struct MPolynomial {
-    pub coefficients: Vec<Fp>,
-    pub powers: Vec<u32>,
+/// Monomial `coefficient * x_1^exponents[0] * ... * x_n^exponents[n - 1]`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Term<F: Field = Fp> {
+    pub coefficient: F,
+    pub exponents: Vec<u32>,
 }
 
-impl MPolynomial {
-    pub fn from(coefficients: Vec<Fp>, powers: Vec<u32>) -> Self {
+impl<F: Field> Term<F> {
+    pub fn eval(&self, x: &[F]) -> F {
+        self.exponents
+            .iter()
+            .zip(x)
+            .fold(self.coefficient, |acc, (power, x)| {
+                acc * x.pow(*power as u64)
+            })
+    }
+}
+
+/// Sparse multivariate polynomial, a sum of `Term`s over `number_of_vars` variables.
+///
+/// Terms are kept normalised: no two terms share exponents, no zero coefficients,
+/// and they are sorted by their exponent vectors.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MPolynomial<F: Field = Fp> {
+    pub terms: Vec<Term<F>>,
+    number_of_vars: usize,
+}
+
+impl<F: Field> MPolynomial<F> {
+    /// Sum of univariate monomials `coefficients[i] * x_i^powers[i]`,
+    /// coefficients past `powers.len()` are added as constants.
+    pub fn from(coefficients: Vec<F>, powers: Vec<u32>) -> Self {
+        let number_of_vars = powers.len();
+
+        let terms = coefficients
+            .into_iter()
+            .enumerate()
+            .map(|(i, coefficient)| {
+                let mut exponents = vec![0; number_of_vars];
+                if i < number_of_vars {
+                    exponents[i] = powers[i];
+                }
+
+                (coefficient, exponents)
+            })
+            .collect();
+
+        Self::from_terms(number_of_vars, terms)
+    }
+
+    pub fn from_terms(number_of_vars: usize, terms: Vec<(F, Vec<u32>)>) -> Self {
+        let mut combined: BTreeMap<Vec<u32>, F> = BTreeMap::new();
+
+        for (coefficient, exponents) in terms {
+            assert_eq!(exponents.len(), number_of_vars);
+
+            let entry = combined.entry(exponents).or_insert(F::zero());
+            *entry = *entry + coefficient;
+        }
+
+        let terms = combined
+            .into_iter()
+            .filter(|(_, coefficient)| *coefficient != F::zero())
+            .map(|(exponents, coefficient)| Term {
+                coefficient,
+                exponents,
+            })
+            .collect();
+
         Self {
-            coefficients,
-            powers,
+            terms,
+            number_of_vars,
         }
     }
 
-    pub fn eval(&self, x: &[Fp]) -> Fp {
-        assert_eq!(x.len(), self.powers.len());
+    pub fn zero(number_of_vars: usize) -> Self {
+        Self {
+            terms: vec![],
+            number_of_vars,
+        }
+    }
 
-        let mut result = Fp(0);
+    pub fn constant(number_of_vars: usize, value: F) -> Self {
+        Self::from_terms(number_of_vars, vec![(value, vec![0; number_of_vars])])
+    }
 
-        for i in 0..self.coefficients.len() {
-            let mut term = self.coefficients[i];
+    /// The polynomial `x_{index + 1}`.
+    pub fn variable(number_of_vars: usize, index: usize) -> Self {
+        let mut exponents = vec![0; number_of_vars];
+        exponents[index] = 1;
 
-            if i < self.powers.len() {
-                result = result + (term * x[i].pow(self.powers[i]))
-            } else {
-                result = result + term;
-            }
+        Self::from_terms(number_of_vars, vec![(F::one(), exponents)])
+    }
+
+    /// The same polynomial with its coefficients embedded in an extension of `F`.
+    pub fn lift<E: ExtensionField<F>>(&self) -> MPolynomial<E> {
+        MPolynomial {
+            terms: self
+                .terms
+                .iter()
+                .map(|term| Term {
+                    coefficient: E::from_base(term.coefficient),
+                    exponents: term.exponents.clone(),
+                })
+                .collect(),
+            number_of_vars: self.number_of_vars,
         }
+    }
 
-        result
+    pub fn eval(&self, x: &[F]) -> F {
+        assert_eq!(x.len(), self.number_of_vars);
+
+        self.terms
+            .iter()
+            .fold(F::zero(), |result, term| result + term.eval(x))
     }
 
-    pub fn degree_ind(&self) -> usize {
-        let mut result: usize = 0;
+    /// Fixes the first `values.len()` variables, the result is a polynomial in the remaining ones.
+    pub fn partial_eval(&self, values: &[F]) -> MPolynomial<F> {
+        assert!(values.len() <= self.number_of_vars);
 
-        for i in 0..self.powers.len() {
-            if self.powers[i] > result as u32 {
-                result = self.powers[i] as usize;
-            }
-        }
+        let terms = self
+            .terms
+            .iter()
+            .map(|term| {
+                let (fixed, free) = term.exponents.split_at(values.len());
+                let coefficient = Term {
+                    coefficient: term.coefficient,
+                    exponents: fixed.to_vec(),
+                }
+                .eval(values);
 
-        result
+                (coefficient, free.to_vec())
+            })
+            .collect();
+
+        Self::from_terms(self.number_of_vars - values.len(), terms)
     }
 
-    pub fn sum_over_hyper_cube(&self, provided_x: Option<Vec<Fp>>) -> Fp {
-        let vars_len = self.powers.len();
-        let mut result = Fp(0);
+    /// Degree in the variable `x_{var + 1}`.
+    pub fn degree(&self, var: usize) -> usize {
+        self.terms
+            .iter()
+            .map(|term| term.exponents[var] as usize)
+            .max()
+            .unwrap_or(0)
+    }
 
+    /// Highest degree over the individual variables.
+    pub fn degree_ind(&self) -> usize {
+        (0..self.number_of_vars)
+            .map(|var| self.degree(var))
+            .max()
+            .unwrap_or(0)
+    }
+
+    pub fn sum_over_hyper_cube(&self, provided_x: Option<Vec<F>>) -> F {
         let mut initial_x = vec![];
-        if provided_x.is_some() {
-            initial_x = provided_x.unwrap();
+        if let Some(setup_x) = provided_x {
+            initial_x = setup_x;
         }
 
-        for i in 0..2u64.pow(vars_len as u32 - initial_x.len() as u32) {
-            let mut x = initial_x.clone();
+        let remaining = self.partial_eval(&initial_x);
+        let vars_len = remaining.number_of_vars();
+        let mut result = F::zero();
 
-            for j in 0..(vars_len - initial_x.len()) {
-                x.push(Fp((i >> j) & 1));
+        for i in 0..2u64.pow(vars_len as u32) {
+            let mut x = vec![];
+
+            for j in 0..vars_len {
+                x.push(F::from_u64((i >> j) & 1));
             }
 
-            result = result + self.eval(&x);
+            result = result + remaining.eval(&x);
         }
 
         result
     }
 
-    pub fn fix_var_over_hyper_cube(
-        &self,
-        provided_x: Option<&Vec<Fp>>,
-    ) -> UPolynomial {
+    /// Evaluations `g(0), g(1), ..., g(d)` of the sumcheck round polynomial, where the
+    /// variable after `provided_x` is kept free and the rest are summed over the hypercube.
+    /// `d` is the degree of that variable.
+    pub fn round_evaluations(&self, provided_x: Option<&Vec<F>>) -> Vec<F> {
         let mut initial_x = vec![];
         if let Some(setup_x) = provided_x {
             initial_x = setup_x.clone();
         }
 
-        let mut first_batch = initial_x.clone();
-        let mut second_batch = initial_x.clone();
+        let remaining = self.partial_eval(&initial_x);
 
-        first_batch.push(Fp(0));
-        second_batch.push(Fp(1));
+        (0..=remaining.degree(0) as u64)
+            .map(|x| remaining.sum_over_hyper_cube(Some(vec![F::from_u64(x)])))
+            .collect()
+    }
 
-        let first_result = self.sum_over_hyper_cube(Some(first_batch));
-        let second_result = self.sum_over_hyper_cube(Some(second_batch));
+    /// Round polynomial of the sumcheck interpolated from `round_evaluations`.
+    pub fn fix_var_over_hyper_cube(&self, provided_x: Option<&Vec<F>>) -> UPolynomial<F> {
+        let evaluations = self.round_evaluations(provided_x);
 
-        UPolynomial::interpolate(vec![(Fp(0), first_result), (Fp(1), second_result)])
+        InterpolationDomain::new(evaluations.len() - 1).interpolate(&evaluations)
     }
 
     pub fn number_of_vars(&self) -> usize {
-        self.powers.len()
+        self.number_of_vars
+    }
+
+    /// Canonical encoding, the number of variables and of terms as little-endian u32s,
+    /// then every term as its exponents (u32 each) followed by its coefficient.
+    ///
+    /// Terms are normalised, so equal polynomials always have the same encoding.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![];
+
+        bytes.extend_from_slice(&(self.number_of_vars as u32).to_le_bytes());
+        bytes.extend_from_slice(&(self.terms.len() as u32).to_le_bytes());
+
+        for term in &self.terms {
+            for exponent in &term.exponents {
+                bytes.extend_from_slice(&exponent.to_le_bytes());
+            }
+
+            bytes.extend_from_slice(&term.coefficient.to_bytes());
+        }
+
+        bytes
+    }
+}
+
+impl<F: Field> Add for MPolynomial<F> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        assert_eq!(self.number_of_vars, rhs.number_of_vars);
+
+        let terms = self
+            .terms
+            .into_iter()
+            .chain(rhs.terms)
+            .map(|term| (term.coefficient, term.exponents))
+            .collect();
+
+        MPolynomial::from_terms(self.number_of_vars, terms)
+    }
+}
+
+impl<F: Field> Neg for MPolynomial<F> {
+    type Output = Self;
+
+    fn neg(mut self) -> Self::Output {
+        for term in self.terms.iter_mut() {
+            term.coefficient = -term.coefficient;
+        }
+
+        self
+    }
+}
+
+impl<F: Field> Sub for MPolynomial<F> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + (-rhs)
+    }
+}
+
+impl<F: Field> Mul for MPolynomial<F> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        assert_eq!(self.number_of_vars, rhs.number_of_vars);
+
+        let mut terms = vec![];
+
+        for a in &self.terms {
+            for b in &rhs.terms {
+                let exponents = a
+                    .exponents
+                    .iter()
+                    .zip(&b.exponents)
+                    .map(|(x, y)| x + y)
+                    .collect();
+
+                terms.push((a.coefficient * b.coefficient, exponents));
+            }
+        }
+
+        MPolynomial::from_terms(self.number_of_vars, terms)
     }
 }
 
@@ -96,6 +299,8 @@ impl MPolynomial {
 mod tests {
     use crate::fp::Fp;
     use crate::mpolynomial::MPolynomial;
+    use crate::proof::{prove_non_interactive, verify_proof};
+    use crate::round_message::round_polynomial_degree;
 
     #[test]
     fn test_eval() {
@@ -126,4 +331,163 @@ mod tests {
 
         assert_eq!(polynomial.sum_over_hyper_cube(Some(vec![Fp(2)])), Fp(60))
     }
+
+    // x1 * x2 * x3 + 2 * x1 * x3 + 3 * x2^2 + 4
+    fn cross_terms() -> MPolynomial {
+        MPolynomial::from_terms(
+            3,
+            vec![
+                (Fp(1), vec![1, 1, 1]),
+                (Fp(2), vec![1, 0, 1]),
+                (Fp(3), vec![0, 2, 0]),
+                (Fp(4), vec![0, 0, 0]),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_eval_cross_terms() {
+        let polynomial = cross_terms();
+
+        assert_eq!(
+            polynomial.eval(&[Fp(2), Fp(3), Fp(5)]),
+            Fp(2 * 3 * 5 + 2 * 2 * 5 + 3 * 9 + 4)
+        );
+    }
+
+    #[test]
+    fn test_from_terms_normalises() {
+        let polynomial = MPolynomial::from_terms(
+            2,
+            vec![
+                (Fp(1), vec![1, 1]),
+                (Fp(2), vec![0, 1]),
+                (Fp(3), vec![1, 1]),
+                (Fp(2).neg(), vec![0, 1]),
+            ],
+        );
+
+        assert_eq!(polynomial.terms.len(), 1);
+        assert_eq!(polynomial.terms[0].coefficient, Fp(4));
+        assert_eq!(polynomial.terms[0].exponents, vec![1, 1]);
+    }
+
+    #[test]
+    fn test_add_and_sub() {
+        let x1 = MPolynomial::variable(2, 0);
+        let x2 = MPolynomial::variable(2, 1);
+
+        let sum = x1.clone() + x2.clone() + MPolynomial::constant(2, Fp(3));
+        assert_eq!(sum.eval(&[Fp(5), Fp(7)]), Fp(15));
+
+        assert_eq!(sum - x1 - x2, MPolynomial::constant(2, Fp(3)));
+    }
+
+    #[test]
+    fn test_mul() {
+        let x1 = MPolynomial::variable(2, 0);
+        let x2 = MPolynomial::variable(2, 1);
+        let one = MPolynomial::constant(2, Fp(1));
+
+        // (x1 + 1) * (x2 + 1) = x1 * x2 + x1 + x2 + 1
+        let product = (x1.clone() + one.clone()) * (x2.clone() + one.clone());
+        let expected = x1.clone() * x2.clone() + x1 + x2 + one;
+
+        assert_eq!(product, expected);
+        assert_eq!(product.eval(&[Fp(2), Fp(3)]), Fp(12));
+    }
+
+    #[test]
+    fn test_degree() {
+        let polynomial = cross_terms();
+
+        assert_eq!(polynomial.degree(0), 1);
+        assert_eq!(polynomial.degree(1), 2);
+        assert_eq!(polynomial.degree(2), 1);
+        assert_eq!(polynomial.degree_ind(), 2);
+
+        assert_eq!(
+            MPolynomial::from(vec![Fp(1), Fp(1)], vec![3, 5]).degree_ind(),
+            5
+        );
+    }
+
+    #[test]
+    fn test_partial_eval() {
+        let polynomial = cross_terms();
+        let partial = polynomial.partial_eval(&[Fp(2)]);
+
+        assert_eq!(partial.number_of_vars(), 2);
+        assert_eq!(
+            partial.eval(&[Fp(3), Fp(5)]),
+            polynomial.eval(&[Fp(2), Fp(3), Fp(5)])
+        );
+    }
+
+    #[test]
+    fn test_sumcheck_with_cross_terms() {
+        // Multilinear with cross terms: x1 * x2 * x3 + 2 * x1 * x3 + 5 * x2 + 1
+        let polynomial = MPolynomial::from_terms(
+            3,
+            vec![
+                (Fp(1), vec![1, 1, 1]),
+                (Fp(2), vec![1, 0, 1]),
+                (Fp(5), vec![0, 1, 0]),
+                (Fp(1), vec![0, 0, 0]),
+            ],
+        );
+
+        let claim = polynomial.sum_over_hyper_cube(None);
+        assert_eq!(claim, Fp(1 + 2 * 2 + 5 * 4 + 8));
+
+        let proof = prove_non_interactive(&polynomial);
+        assert!(verify_proof(&polynomial, &proof, claim).is_ok());
+    }
+
+    #[test]
+    fn test_fix_var_over_hyper_cube_degree() {
+        let polynomial = MPolynomial::from(vec![Fp(2), Fp(3), Fp(5), Fp(7)], vec![3, 2, 5]);
+
+        let mut randomness = vec![];
+        for (expected_degree, r) in [(3, Fp(11)), (2, Fp(13)), (5, Fp(17))] {
+            let round_polynomial = polynomial.fix_var_over_hyper_cube(Some(&randomness));
+            assert_eq!(round_polynomial_degree(&round_polynomial), expected_degree);
+
+            randomness.push(r);
+        }
+    }
+
+    #[test]
+    fn test_fix_var_over_hyper_cube_evaluations() {
+        // x1^3 * x2 + 4 * x2^2
+        let polynomial = MPolynomial::from_terms(2, vec![(Fp(1), vec![3, 1]), (Fp(4), vec![0, 2])]);
+        let round_polynomial = polynomial.fix_var_over_hyper_cube(None);
+
+        // g(x) = x^3 + 4, every point has to match, not only 0 and 1
+        for x in [0, 1, 2, 7] {
+            assert_eq!(round_polynomial.evaluate(Fp(x)), Fp(x * x * x + 4));
+        }
+    }
+
+    #[test]
+    fn test_sumcheck_with_higher_degrees() {
+        let polynomial = MPolynomial::from(vec![Fp(2), Fp(3), Fp(5), Fp(7)], vec![3, 2, 5]);
+        let claim = polynomial.sum_over_hyper_cube(None);
+
+        let proof = prove_non_interactive(&polynomial);
+        assert!(verify_proof(&polynomial, &proof, claim).is_ok());
+
+        let polynomial = MPolynomial::from_terms(
+            3,
+            vec![
+                (Fp(3), vec![2, 1, 3]),
+                (Fp(1), vec![0, 4, 1]),
+                (Fp(9), vec![1, 0, 0]),
+            ],
+        );
+        let claim = polynomial.sum_over_hyper_cube(None);
+
+        let proof = prove_non_interactive(&polynomial);
+        assert!(verify_proof(&polynomial, &proof, claim).is_ok());
+    }
 }
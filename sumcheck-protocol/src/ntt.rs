@@ -0,0 +1,182 @@
+use crate::field::Field;
+use crate::fp::Fp;
+
+/// Evaluates the polynomial with coefficients `values`, lowest degree first, at
+/// `w^0, ..., w^(n - 1)` in place, `w` being the primitive `n`-th root of unity.
+pub fn ntt(values: &mut [Fp]) {
+    let root = root_of_unity(values.len());
+
+    transform(values, root);
+}
+
+/// Inverse of `ntt`, interpolates the coefficients from evaluations over `<w>`.
+pub fn intt(values: &mut [Fp]) {
+    let root = root_of_unity(values.len()).inverse();
+
+    transform(values, root);
+
+    let n_inverse = Fp::from(values.len() as u64).inverse();
+    for value in values.iter_mut() {
+        *value = *value * n_inverse;
+    }
+}
+
+/// Same as `ntt`, over the coset `shift * <w>`.
+pub fn coset_ntt(values: &mut [Fp], shift: Fp) {
+    scale(values, shift);
+    ntt(values);
+}
+
+/// Inverse of `coset_ntt`.
+pub fn coset_intt(values: &mut [Fp], shift: Fp) {
+    intt(values);
+    scale(values, shift.inverse());
+}
+
+/// Product of two polynomials given by their coefficients, lowest degree first.
+pub fn multiply(a: &[Fp], b: &[Fp]) -> Vec<Fp> {
+    if a.is_empty() || b.is_empty() {
+        return vec![];
+    }
+
+    let len = a.len() + b.len() - 1;
+    let size = len.next_power_of_two();
+
+    let mut a = a.to_vec();
+    let mut b = b.to_vec();
+    a.resize(size, Fp::zero());
+    b.resize(size, Fp::zero());
+
+    ntt(&mut a);
+    ntt(&mut b);
+
+    for (a_i, b_i) in a.iter_mut().zip(&b) {
+        *a_i = *a_i * *b_i;
+    }
+
+    intt(&mut a);
+    a.truncate(len);
+
+    a
+}
+
+fn root_of_unity(size: usize) -> Fp {
+    assert!(size.is_power_of_two(), "size must be a power of two");
+
+    Fp::two_adic_root_of_unity(size.trailing_zeros() as usize)
+}
+
+/// `values[j] * shift^j`
+fn scale(values: &mut [Fp], shift: Fp) {
+    let mut power = Fp::one();
+
+    for value in values.iter_mut() {
+        *value = *value * power;
+        power = power * shift;
+    }
+}
+
+/// Iterative radix-2 Cooley-Tukey on bit-reversed input.
+fn transform(values: &mut [Fp], root: Fp) {
+    let n = values.len();
+    if n <= 1 {
+        return;
+    }
+
+    let log_n = n.trailing_zeros();
+    for i in 0..n {
+        let j = i.reverse_bits() >> (usize::BITS - log_n);
+        if i < j {
+            values.swap(i, j);
+        }
+    }
+
+    let mut len = 2;
+    while len <= n {
+        let step = root.pow((n / len) as u64);
+        let twiddles: Vec<Fp> = (0..len / 2)
+            .scan(Fp::one(), |w, _| {
+                let current = *w;
+                *w = *w * step;
+
+                Some(current)
+            })
+            .collect();
+
+        for chunk in values.chunks_mut(len) {
+            let (low, high) = chunk.split_at_mut(len / 2);
+
+            for ((u, v), w) in low.iter_mut().zip(high.iter_mut()).zip(&twiddles) {
+                let t = *v * *w;
+
+                *v = *u - t;
+                *u = *u + t;
+            }
+        }
+
+        len <<= 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::field::{schoolbook_convolve, Field};
+    use crate::fp::Fp;
+    use crate::ntt::{coset_intt, coset_ntt, intt, multiply, ntt};
+
+    fn eval(coefficients: &[Fp], x: Fp) -> Fp {
+        coefficients
+            .iter()
+            .rev()
+            .fold(Fp(0), |acc, coefficient| acc * x + *coefficient)
+    }
+
+    fn random(len: usize) -> Vec<Fp> {
+        (0..len).map(|_| Fp::sample()).collect()
+    }
+
+    #[test]
+    fn test_ntt_evaluates_on_subgroup() {
+        for log_n in 0..6 {
+            let coefficients = random(1 << log_n);
+            let root = Fp::two_adic_root_of_unity(log_n);
+
+            let mut values = coefficients.clone();
+            ntt(&mut values);
+
+            for (i, value) in values.iter().enumerate() {
+                assert_eq!(*value, eval(&coefficients, root.pow(i as u64)));
+            }
+
+            intt(&mut values);
+            assert_eq!(values, coefficients);
+        }
+    }
+
+    #[test]
+    fn test_coset_ntt() {
+        let coefficients = random(16);
+        let shift = Fp::GENERATOR;
+        let root = Fp::two_adic_root_of_unity(4);
+
+        let mut values = coefficients.clone();
+        coset_ntt(&mut values, shift);
+
+        for (i, value) in values.iter().enumerate() {
+            assert_eq!(*value, eval(&coefficients, shift * root.pow(i as u64)));
+        }
+
+        coset_intt(&mut values, shift);
+        assert_eq!(values, coefficients);
+    }
+
+    #[test]
+    fn test_multiply_matches_schoolbook() {
+        for (a_len, b_len) in [(0, 3), (1, 1), (1, 7), (5, 3), (16, 16), (33, 20)] {
+            let a = random(a_len);
+            let b = random(b_len);
+
+            assert_eq!(multiply(&a, &b), schoolbook_convolve(&a, &b));
+        }
+    }
+}
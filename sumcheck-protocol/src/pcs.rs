@@ -0,0 +1,265 @@
+use crate::error::SumcheckError;
+use crate::field::Field;
+use crate::fp::Fp;
+use crate::mle::DenseMultilinearExtension;
+use crate::proof::{prove_rounds, SumcheckProof};
+use crate::prover::{MultilinearProver, Prover};
+use crate::transcript::Transcript;
+use crate::verifier::SumcheckVerifier;
+use sha2::{Digest, Sha256};
+use std::fmt::Debug;
+
+/// Commitment to a multilinear polynomial that can later be opened at any point.
+///
+/// `open` and `verify_opening` share the transcript of the surrounding protocol,
+/// schemes that need challenges draw them from it. `commit` also returns whatever
+/// the prover keeps to open the commitment later without recomputing it.
+pub trait PolynomialCommitmentScheme<F: Field = Fp> {
+    type Commitment: Clone + Debug + PartialEq + AsRef<[u8]>;
+    type Opening: Clone + Debug;
+    type ProverData;
+
+    fn commit(
+        &self,
+        polynomial: &DenseMultilinearExtension<F>,
+    ) -> (Self::Commitment, Self::ProverData);
+
+    /// Evaluates `polynomial` at `point` and proves the value, `data` is what `commit`
+    /// returned for `polynomial`.
+    fn open(
+        &self,
+        polynomial: &DenseMultilinearExtension<F>,
+        data: &Self::ProverData,
+        point: &[F],
+        transcript: &mut Transcript,
+    ) -> (F, Self::Opening);
+
+    /// Accepts only if the committed polynomial evaluates to `value` at `point`.
+    fn verify_opening(
+        &self,
+        commitment: &Self::Commitment,
+        point: &[F],
+        value: F,
+        opening: &Self::Opening,
+        transcript: &mut Transcript,
+    ) -> bool;
+}
+
+/// Transparent baseline scheme: the commitment is a SHA-256 hash of the evaluation
+/// table and an opening reveals the whole table.
+///
+/// Binding and free of any setup, but openings are as large as the polynomial.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HashCommitment;
+
+impl HashCommitment {
+    fn hash<F: Field>(evaluations: &[F]) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+
+        hasher.update((evaluations.len() as u64).to_le_bytes());
+        for evaluation in evaluations {
+            hasher.update(evaluation.to_bytes());
+        }
+
+        hasher.finalize().into()
+    }
+}
+
+impl<F: Field> PolynomialCommitmentScheme<F> for HashCommitment {
+    type Commitment = [u8; 32];
+    type Opening = Vec<F>;
+    type ProverData = ();
+
+    fn commit(&self, polynomial: &DenseMultilinearExtension<F>) -> (Self::Commitment, ()) {
+        (Self::hash(&polynomial.evaluations), ())
+    }
+
+    fn open(
+        &self,
+        polynomial: &DenseMultilinearExtension<F>,
+        _data: &(),
+        point: &[F],
+        _transcript: &mut Transcript,
+    ) -> (F, Self::Opening) {
+        (polynomial.eval(point), polynomial.evaluations.clone())
+    }
+
+    fn verify_opening(
+        &self,
+        commitment: &Self::Commitment,
+        point: &[F],
+        value: F,
+        opening: &Self::Opening,
+        _transcript: &mut Transcript,
+    ) -> bool {
+        if opening.len() != 1 << point.len() || Self::hash(opening) != *commitment {
+            return false;
+        }
+
+        DenseMultilinearExtension::from(opening.clone()).eval(point) == value
+    }
+}
+
+/// Sumcheck proof whose final evaluation is backed by an opening of a commitment.
+#[derive(Debug, Clone)]
+pub struct CommittedSumcheckProof<F: Field, S: PolynomialCommitmentScheme<F>> {
+    pub proof: SumcheckProof<F>,
+    pub opening: S::Opening,
+}
+
+/// Committed polynomials are multilinear tables, every round polynomial is a line.
+const DEGREE: usize = 1;
+
+fn new_transcript<F: Field>(
+    number_of_vars: usize,
+    claimed_sum: &F,
+    commitment: &[u8],
+) -> Transcript {
+    let mut transcript = Transcript::new(b"committed_sumcheck");
+
+    transcript.absorb_u64(b"number_of_vars", number_of_vars as u64);
+    transcript.absorb_u64(b"degree", DEGREE as u64);
+    transcript.absorb_field(b"claimed_sum", claimed_sum);
+    transcript.absorb_bytes(b"commitment", commitment);
+
+    transcript
+}
+
+/// Proves the sum of a committed multilinear polynomial over the hypercube, only
+/// multilinear tables can be committed so the degree is always `DEGREE`.
+/// `commitment` and `data` are what `scheme.commit` returned.
+pub fn prove_committed<F: Field, S: PolynomialCommitmentScheme<F>>(
+    scheme: &S,
+    polynomial: &DenseMultilinearExtension<F>,
+    commitment: &S::Commitment,
+    data: &S::ProverData,
+) -> CommittedSumcheckProof<F, S> {
+    let mut prover = MultilinearProver::new(polynomial.clone());
+    let claimed_sum = prover.claimed_sum();
+    assert_eq!(prover.degree(), DEGREE);
+
+    let mut transcript = new_transcript(
+        polynomial.number_of_vars(),
+        &claimed_sum,
+        commitment.as_ref(),
+    );
+
+    let (round_messages, randomness) = prove_rounds(&mut prover, &mut transcript);
+
+    let (final_evaluation, opening) = scheme.open(polynomial, data, &randomness, &mut transcript);
+
+    CommittedSumcheckProof {
+        proof: SumcheckProof {
+            claimed_sum,
+            round_messages,
+            final_evaluation,
+        },
+        opening,
+    }
+}
+
+/// Checks `proof` knowing only the commitment, the final oracle query is answered
+/// by the opening instead of evaluating the polynomial.
+pub fn verify_committed<F: Field, S: PolynomialCommitmentScheme<F>>(
+    scheme: &S,
+    commitment: &S::Commitment,
+    number_of_vars: usize,
+    claim: F,
+    proof: &CommittedSumcheckProof<F, S>,
+) -> Result<Vec<F>, SumcheckError> {
+    let mut transcript = new_transcript(number_of_vars, &claim, commitment.as_ref());
+
+    // The claimed value is only trusted until the opening is checked below
+    let final_evaluation = proof.proof.final_evaluation;
+    let randomness = SumcheckVerifier::new(claim, number_of_vars, DEGREE, |_| final_evaluation)
+        .verify_proof_with_transcript(&proof.proof, &mut transcript)?;
+
+    if !scheme.verify_opening(
+        commitment,
+        &randomness,
+        final_evaluation,
+        &proof.opening,
+        &mut transcript,
+    ) {
+        return Err(SumcheckError::InvalidOpening);
+    }
+
+    Ok(randomness)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::SumcheckError;
+    use crate::fp::Fp;
+    use crate::mle::DenseMultilinearExtension;
+    use crate::pcs::{
+        prove_committed, verify_committed, HashCommitment, PolynomialCommitmentScheme,
+    };
+    use crate::transcript::Transcript;
+
+    fn table() -> DenseMultilinearExtension {
+        DenseMultilinearExtension::from((0..1u64 << 6).map(|i| Fp(i * i + 7)).collect())
+    }
+
+    #[test]
+    fn test_hash_commitment_opening() {
+        let table = table();
+        let (commitment, _) = HashCommitment.commit(&table);
+        let point = [Fp(2), Fp(3), Fp(5), Fp(7), Fp(11), Fp(13)];
+
+        let (value, opening) =
+            HashCommitment.open(&table, &(), &point, &mut Transcript::new(b"test"));
+        assert_eq!(value, table.eval(&point));
+
+        let verify = |value, opening: &Vec<Fp>| {
+            HashCommitment.verify_opening(
+                &commitment,
+                &point,
+                value,
+                opening,
+                &mut Transcript::new(b"test"),
+            )
+        };
+
+        assert!(verify(value, &opening));
+        assert!(!verify(value + Fp(1), &opening));
+
+        let mut tampered = opening.clone();
+        tampered[3] = tampered[3] + Fp(1);
+        assert!(!verify(value, &tampered));
+    }
+
+    #[test]
+    fn test_committed_sumcheck() {
+        let table = table();
+        let claim = table.sum_over_hyper_cube();
+        let (commitment, _) = HashCommitment.commit(&table);
+
+        let proof = prove_committed(&HashCommitment, &table, &commitment, &());
+        let randomness = verify_committed(&HashCommitment, &commitment, 6, claim, &proof).unwrap();
+
+        assert_eq!(proof.proof.final_evaluation, table.eval(&randomness));
+    }
+
+    #[test]
+    fn test_committed_sumcheck_rejects_other_polynomial() {
+        let table = table();
+        let claim = table.sum_over_hyper_cube();
+        let (commitment, _) = HashCommitment.commit(&table);
+
+        // Same sum, different polynomial
+        let mut other = table.clone();
+        other.evaluations[0] = other.evaluations[0] + Fp(1);
+        other.evaluations[1] -= Fp(1);
+
+        let proof = prove_committed(&HashCommitment, &other, &commitment, &());
+        assert_eq!(
+            verify_committed(&HashCommitment, &commitment, 6, claim, &proof).err(),
+            Some(SumcheckError::InvalidOpening)
+        );
+
+        let mut proof = prove_committed(&HashCommitment, &table, &commitment, &());
+        proof.proof.final_evaluation = proof.proof.final_evaluation + Fp(1);
+        assert!(verify_committed(&HashCommitment, &commitment, 6, claim, &proof).is_err());
+    }
+}
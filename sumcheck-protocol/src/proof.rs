@@ -0,0 +1,410 @@
+use crate::error::{DecodingError, SumcheckError};
+use crate::field::Field;
+use crate::fp::Fp;
+use crate::mpolynomial::MPolynomial;
+use crate::prover::{Prover, SumcheckProver};
+use crate::round_message::RoundMessage;
+use crate::transcript::Transcript;
+use crate::verifier::SumcheckVerifier;
+
+/// Non-interactive sumcheck proof, challenges are derived from a `Transcript`.
+#[derive(Debug, Clone)]
+pub struct SumcheckProof<F: Field = Fp> {
+    pub claimed_sum: F,
+    pub round_messages: Vec<RoundMessage<F>>,
+    pub final_evaluation: F,
+}
+
+impl<F: Field> SumcheckProof<F> {
+    pub const ENCODING_VERSION: u8 = 2;
+
+    /// Canonical binary encoding, all integers are little-endian:
+    ///
+    /// | field                         | size                           |
+    /// |-------------------------------|--------------------------------|
+    /// | version (`ENCODING_VERSION`)  | 1 byte                         |
+    /// | claimed sum                   | `F::NUM_BYTES`                 |
+    /// | number of rounds              | 4 bytes (u32)                  |
+    /// | per round: evaluations count  | 4 bytes (u32)                  |
+    /// | per round: evaluations        | `F::NUM_BYTES` each, as stored |
+    /// | final evaluation              | `F::NUM_BYTES`                 |
+    ///
+    /// Field elements are written with `Field::to_bytes`, for `Fp` that is the value
+    /// reduced below `Fp::MODULO` as a little-endian u64.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![Self::ENCODING_VERSION];
+
+        bytes.extend_from_slice(&self.claimed_sum.to_bytes());
+        bytes.extend_from_slice(&(self.round_messages.len() as u32).to_le_bytes());
+
+        for round_message in &self.round_messages {
+            bytes.extend_from_slice(&(round_message.evaluations.len() as u32).to_le_bytes());
+
+            for evaluation in &round_message.evaluations {
+                bytes.extend_from_slice(&evaluation.to_bytes());
+            }
+        }
+
+        bytes.extend_from_slice(&self.final_evaluation.to_bytes());
+
+        bytes
+    }
+
+    /// Decodes a proof written by `to_bytes`, anything but the exact canonical encoding is rejected.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DecodingError> {
+        let mut reader = Reader { bytes };
+
+        let version = reader.read::<1>()?[0];
+        if version != Self::ENCODING_VERSION {
+            return Err(DecodingError::UnsupportedVersion(version));
+        }
+
+        let claimed_sum = reader.read_field()?;
+
+        let rounds = reader.read_u32()?;
+        let mut round_messages = vec![];
+
+        for _ in 0..rounds {
+            let len = reader.read_u32()?;
+            let mut evaluations = vec![];
+
+            for _ in 0..len {
+                evaluations.push(reader.read_field()?);
+            }
+
+            round_messages.push(RoundMessage { evaluations });
+        }
+
+        let final_evaluation = reader.read_field()?;
+
+        if !reader.bytes.is_empty() {
+            return Err(DecodingError::TrailingBytes);
+        }
+
+        Ok(Self {
+            claimed_sum,
+            round_messages,
+            final_evaluation,
+        })
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl Reader<'_> {
+    fn read<const N: usize>(&mut self) -> Result<[u8; N], DecodingError> {
+        if self.bytes.len() < N {
+            return Err(DecodingError::UnexpectedEnd);
+        }
+
+        let (head, tail) = self.bytes.split_at(N);
+        self.bytes = tail;
+
+        Ok(head.try_into().unwrap())
+    }
+
+    fn read_u32(&mut self) -> Result<u32, DecodingError> {
+        Ok(u32::from_le_bytes(self.read::<4>()?))
+    }
+
+    fn read_field<F: Field>(&mut self) -> Result<F, DecodingError> {
+        if self.bytes.len() < F::NUM_BYTES {
+            return Err(DecodingError::UnexpectedEnd);
+        }
+
+        let (head, tail) = self.bytes.split_at(F::NUM_BYTES);
+        self.bytes = tail;
+
+        F::from_bytes(head).ok_or(DecodingError::NonCanonicalElement)
+    }
+}
+
+/// Starts the transcript of a standalone sumcheck.
+///
+/// `statement` identifies the polynomial, e.g. its canonical encoding or a commitment
+/// to it, so that the challenges depend on the instance and not only on its shape.
+pub(crate) fn new_transcript<F: Field>(
+    statement: &[u8],
+    number_of_vars: usize,
+    degree: usize,
+    claimed_sum: &F,
+) -> Transcript {
+    let mut transcript = Transcript::new(b"sumcheck");
+
+    transcript.absorb_bytes(b"statement", statement);
+    transcript.absorb_u64(b"number_of_vars", number_of_vars as u64);
+    transcript.absorb_u64(b"degree", degree as u64);
+    transcript.absorb_field(b"claimed_sum", claimed_sum);
+
+    transcript
+}
+
+pub fn prove_non_interactive<F: Field>(polynomial: &MPolynomial<F>) -> SumcheckProof<F> {
+    prove(
+        SumcheckProver::new(polynomial.clone()),
+        &polynomial.to_bytes(),
+    )
+}
+
+/// Runs any `Prover` against the Fiat-Shamir transcript, `statement` has to be
+/// the one given to `SumcheckVerifier::verify_proof`.
+pub fn prove<F: Field, P: Prover<F>>(mut prover: P, statement: &[u8]) -> SumcheckProof<F> {
+    let claimed_sum = prover.claimed_sum();
+    let mut transcript = new_transcript(
+        statement,
+        prover.number_of_vars(),
+        prover.degree(),
+        &claimed_sum,
+    );
+
+    let (round_messages, _) = prove_rounds(&mut prover, &mut transcript);
+
+    SumcheckProof {
+        claimed_sum,
+        round_messages,
+        final_evaluation: prover.final_evaluation(),
+    }
+}
+
+/// Runs every round of `prover` on a transcript shared with other sub-protocols,
+/// returns the round messages and the challenges they were answered with.
+pub(crate) fn prove_rounds<F: Field, P: Prover<F>>(
+    prover: &mut P,
+    transcript: &mut Transcript,
+) -> (Vec<RoundMessage<F>>, Vec<F>) {
+    let mut challenge = None;
+    let mut round_messages = vec![];
+    let mut randomness = vec![];
+
+    while let Some(evaluations) = prover.round_evaluations(challenge) {
+        let round_message = RoundMessage::from_evaluations(&evaluations);
+
+        transcript.absorb_round_message(b"round_message", &round_message);
+        let next = transcript.challenge(b"challenge");
+
+        challenge = Some(next);
+        randomness.push(next);
+        round_messages.push(round_message);
+    }
+
+    (round_messages, randomness)
+}
+
+/// Checks `proof` against `claim` and returns the challenges the proof was bound to.
+///
+/// Unlike a bare `(proof, claim)` check this takes the polynomial: it is the statement
+/// the transcript is bound to, and the final evaluation has to be checked against it
+/// at the random point. Verifiers holding only a commitment or an oracle use
+/// `SumcheckVerifier::verify_proof`, or `verify_committed`.
+pub fn verify_proof<F: Field>(
+    polynomial: &MPolynomial<F>,
+    proof: &SumcheckProof<F>,
+    claim: F,
+) -> Result<Vec<F>, SumcheckError> {
+    SumcheckVerifier::new(
+        claim,
+        polynomial.number_of_vars(),
+        polynomial.degree_ind(),
+        |x| polynomial.eval(x),
+    )
+    .verify_proof(proof, &polynomial.to_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::{DecodingError, SumcheckError};
+    use crate::fp::{FiniteField, Fp};
+    use crate::mpolynomial::MPolynomial;
+    use crate::proof::{new_transcript, prove_non_interactive, verify_proof, SumcheckProof};
+    use crate::prover::{Prover, SumcheckProver};
+    use crate::round_message::RoundMessage;
+
+    fn polynomial() -> MPolynomial {
+        MPolynomial::from(vec![Fp(2), Fp(3), Fp(5), Fp(7)], vec![1, 1, 1])
+    }
+
+    #[test]
+    fn test_prove_and_verify() {
+        let polynomial = polynomial();
+        let proof = prove_non_interactive(&polynomial);
+
+        assert_eq!(proof.claimed_sum, Fp(96));
+        assert_eq!(proof.round_messages.len(), 3);
+        assert!(verify_proof(&polynomial, &proof, Fp(96)).is_ok());
+    }
+
+    #[test]
+    fn test_proof_is_deterministic() {
+        let polynomial = polynomial();
+
+        let first = prove_non_interactive(&polynomial);
+        let second = prove_non_interactive(&polynomial);
+
+        assert_eq!(first.final_evaluation, second.final_evaluation);
+        assert_eq!(
+            verify_proof(&polynomial, &first, Fp(96)),
+            verify_proof(&polynomial, &second, Fp(96))
+        );
+    }
+
+    #[test]
+    fn test_reject_wrong_claim() {
+        let polynomial = polynomial();
+        let mut proof = prove_non_interactive(&polynomial);
+
+        assert_eq!(
+            verify_proof(&polynomial, &proof, Fp(97)),
+            Err(SumcheckError::ClaimMismatch)
+        );
+
+        // g(1) is derived from the claim, so the lie carries through every round
+        // and only the final evaluation catches it
+        proof.claimed_sum = Fp(97);
+        assert_eq!(
+            verify_proof(&polynomial, &proof, Fp(97)),
+            Err(SumcheckError::FinalEvaluationMismatch)
+        );
+    }
+
+    #[test]
+    fn test_transcript_binds_statement() {
+        // Same shape, degree and sum, only the polynomial differs
+        let a = polynomial();
+        let b = MPolynomial::from(vec![Fp(3), Fp(2), Fp(5), Fp(7)], vec![1, 1, 1]);
+        assert_eq!(a.sum_over_hyper_cube(None), b.sum_over_hyper_cube(None));
+
+        let challenge = |polynomial: &MPolynomial| {
+            new_transcript(&polynomial.to_bytes(), 3, 1, &Fp(96)).challenge::<Fp>(b"challenge")
+        };
+        assert_ne!(challenge(&a), challenge(&b));
+
+        let proof = prove_non_interactive(&a);
+        assert!(verify_proof(&b, &proof, Fp(96)).is_err());
+    }
+
+    #[test]
+    fn test_reject_tampered_round() {
+        let polynomial = polynomial();
+        let mut proof = prove_non_interactive(&polynomial);
+
+        proof.round_messages[1].evaluations[0] = proof.round_messages[1].evaluations[0] + Fp(1);
+
+        assert!(verify_proof(&polynomial, &proof, Fp(96)).is_err());
+    }
+
+    #[test]
+    fn test_reject_tampered_final_evaluation() {
+        let polynomial = polynomial();
+        let mut proof = prove_non_interactive(&polynomial);
+
+        proof.final_evaluation = proof.final_evaluation + Fp(1);
+
+        assert_eq!(
+            verify_proof(&polynomial, &proof, Fp(96)),
+            Err(SumcheckError::FinalEvaluationMismatch)
+        );
+    }
+
+    #[test]
+    fn test_encoding_round_trip() {
+        let polynomial = polynomial();
+        let proof = prove_non_interactive(&polynomial);
+
+        let bytes = proof.to_bytes();
+        let decoded = SumcheckProof::<Fp>::from_bytes(&bytes).unwrap();
+
+        assert_eq!(decoded.claimed_sum, proof.claimed_sum);
+        assert_eq!(decoded.final_evaluation, proof.final_evaluation);
+        assert_eq!(decoded.round_messages, proof.round_messages);
+
+        assert_eq!(decoded.to_bytes(), bytes);
+        assert!(verify_proof(&polynomial, &decoded, Fp(96)).is_ok());
+    }
+
+    #[test]
+    fn test_encoding_layout() {
+        let proof = SumcheckProof {
+            claimed_sum: Fp(3),
+            round_messages: vec![RoundMessage {
+                evaluations: vec![Fp(1), Fp(2)],
+            }],
+            final_evaluation: Fp(5),
+        };
+
+        let mut expected = vec![SumcheckProof::<Fp>::ENCODING_VERSION];
+        expected.extend_from_slice(&3u64.to_le_bytes());
+        expected.extend_from_slice(&1u32.to_le_bytes());
+        expected.extend_from_slice(&2u32.to_le_bytes());
+        expected.extend_from_slice(&1u64.to_le_bytes());
+        expected.extend_from_slice(&2u64.to_le_bytes());
+        expected.extend_from_slice(&5u64.to_le_bytes());
+
+        assert_eq!(proof.to_bytes(), expected);
+    }
+
+    #[test]
+    fn test_decoding_rejects_malformed_input() {
+        let bytes = prove_non_interactive(&polynomial()).to_bytes();
+
+        let mut wrong_version = bytes.clone();
+        wrong_version[0] = 1;
+        assert_eq!(
+            SumcheckProof::<Fp>::from_bytes(&wrong_version).err(),
+            Some(DecodingError::UnsupportedVersion(1))
+        );
+
+        assert_eq!(
+            SumcheckProof::<Fp>::from_bytes(&bytes[..bytes.len() - 1]).err(),
+            Some(DecodingError::UnexpectedEnd)
+        );
+        assert_eq!(
+            SumcheckProof::<Fp>::from_bytes(&[]).err(),
+            Some(DecodingError::UnexpectedEnd)
+        );
+
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert_eq!(
+            SumcheckProof::<Fp>::from_bytes(&trailing).err(),
+            Some(DecodingError::TrailingBytes)
+        );
+
+        let mut non_canonical = bytes.clone();
+        non_canonical[1..9].copy_from_slice(&Fp::MODULO.to_le_bytes());
+        assert_eq!(
+            SumcheckProof::<Fp>::from_bytes(&non_canonical).err(),
+            Some(DecodingError::NonCanonicalElement)
+        );
+    }
+
+    #[test]
+    fn test_compressed_proof_size() {
+        let polynomial = MPolynomial::from(vec![Fp(2), Fp(3), Fp(5), Fp(7)], vec![3, 2, 5]);
+        let proof = prove_non_interactive(&polynomial);
+
+        // The same rounds sent as full coefficient vectors
+        let mut prover = SumcheckProver::new(polynomial.clone());
+        let mut coefficients = 0;
+        let mut challenge = None;
+        while let Some(round_polynomial) = prover.round(challenge) {
+            coefficients += round_polynomial.coefficients.len();
+            challenge = Some(Fp(3));
+        }
+
+        let evaluations: usize = proof
+            .round_messages
+            .iter()
+            .map(|message| message.evaluations.len())
+            .sum();
+
+        // One field element saved per round
+        assert_eq!(coefficients, 4 + 3 + 6);
+        assert_eq!(evaluations, coefficients - 3);
+        assert_eq!(
+            proof.to_bytes().len(),
+            1 + 8 + 4 + 3 * 4 + evaluations * 8 + 8
+        );
+    }
+}
@@ -0,0 +1,600 @@
+use crate::field::{ExtensionField, Field};
+use crate::fp::Fp;
+use crate::interpolation::InterpolationDomain;
+use crate::mle::DenseMultilinearExtension;
+use crate::mpolynomial::MPolynomial;
+use crate::upolynomial::UPolynomial;
+use crate::virtual_polynomial::{combine, Product, VirtualPolynomial};
+
+/// Prover side of the sumcheck protocol, the only party holding the polynomial.
+pub trait Prover<F: Field = Fp> {
+    fn claimed_sum(&self) -> F;
+
+    fn number_of_vars(&self) -> usize;
+
+    /// Upper bound on the degree of every round polynomial.
+    fn degree(&self) -> usize;
+
+    /// Produces the evaluations `g(0), g(1), ..., g(d)` of the next round polynomial.
+    ///
+    /// `challenge` is the verifier's answer to the previous round polynomial and must be
+    /// `None` for the first round. Returns `None` once every variable is fixed, the
+    /// challenge passed to that last call is still recorded for `final_evaluation`.
+    fn round_evaluations(&mut self, challenge: Option<F>) -> Option<Vec<F>>;
+
+    /// Same as `round_evaluations`, interpolated into the round polynomial.
+    fn round(&mut self, challenge: Option<F>) -> Option<UPolynomial<F>> {
+        let evaluations = self.round_evaluations(challenge)?;
+
+        Some(InterpolationDomain::new(evaluations.len() - 1).interpolate(&evaluations))
+    }
+
+    /// Evaluation of the polynomial at the challenges received so far.
+    fn final_evaluation(&self) -> F;
+}
+
+/// Prover for an arbitrary `MPolynomial`, every round re-sums over the hypercube.
+pub struct SumcheckProver<F: Field = Fp> {
+    polynomial: MPolynomial<F>,
+    randomness: Vec<F>,
+}
+
+impl<F: Field> SumcheckProver<F> {
+    pub fn new(polynomial: MPolynomial<F>) -> Self {
+        Self {
+            polynomial,
+            randomness: vec![],
+        }
+    }
+}
+
+impl<F: Field> Prover<F> for SumcheckProver<F> {
+    fn claimed_sum(&self) -> F {
+        self.polynomial.sum_over_hyper_cube(None)
+    }
+
+    fn number_of_vars(&self) -> usize {
+        self.polynomial.number_of_vars()
+    }
+
+    fn degree(&self) -> usize {
+        self.polynomial.degree_ind()
+    }
+
+    fn round_evaluations(&mut self, challenge: Option<F>) -> Option<Vec<F>> {
+        if let Some(challenge) = challenge {
+            self.randomness.push(challenge);
+        }
+
+        if self.randomness.len() >= self.polynomial.number_of_vars() {
+            return None;
+        }
+
+        Some(self.polynomial.round_evaluations(Some(&self.randomness)))
+    }
+
+    fn final_evaluation(&self) -> F {
+        self.polynomial.eval(&self.randomness)
+    }
+}
+
+/// Linear-time prover for a multilinear polynomial.
+///
+/// Every challenge folds the evaluation table in half, so all rounds together
+/// take `O(2^n)` field operations.
+pub struct MultilinearProver<F: Field = Fp> {
+    table: DenseMultilinearExtension<F>,
+    number_of_vars: usize,
+}
+
+impl<F: Field> MultilinearProver<F> {
+    pub fn new(table: DenseMultilinearExtension<F>) -> Self {
+        Self {
+            number_of_vars: table.number_of_vars(),
+            table,
+        }
+    }
+}
+
+impl<F: Field> Prover<F> for MultilinearProver<F> {
+    fn claimed_sum(&self) -> F {
+        self.table.sum_over_hyper_cube()
+    }
+
+    fn number_of_vars(&self) -> usize {
+        self.number_of_vars
+    }
+
+    fn degree(&self) -> usize {
+        1
+    }
+
+    fn round_evaluations(&mut self, challenge: Option<F>) -> Option<Vec<F>> {
+        if let Some(challenge) = challenge {
+            self.table.fix_first_variable(challenge);
+        }
+
+        if self.table.number_of_vars() == 0 {
+            return None;
+        }
+
+        Some(multilinear_round(&self.table.evaluations))
+    }
+
+    fn final_evaluation(&self) -> F {
+        self.table.evaluations[0]
+    }
+}
+
+/// `g(0)` and `g(1)` of a multilinear round, the sums of the even and the odd entries.
+fn multilinear_round<F: Field>(evaluations: &[F]) -> Vec<F> {
+    let mut at_zero = F::zero();
+    let mut at_one = F::zero();
+
+    for pair in evaluations.chunks(2) {
+        at_zero = at_zero + pair[0];
+        at_one = at_one + pair[1];
+    }
+
+    vec![at_zero, at_one]
+}
+
+/// Prover for `sum_x f_1(x) * ... * f_k(x)` with every `f_i` multilinear.
+///
+/// Each round evaluates the restriction of every `f_i` at `0..=k` and multiplies
+/// pointwise, then every table is folded with the challenge. The degree `k`
+/// product is never materialised.
+pub struct ProductSumcheck<F: Field = Fp> {
+    tables: Vec<DenseMultilinearExtension<F>>,
+    number_of_vars: usize,
+}
+
+impl<F: Field> ProductSumcheck<F> {
+    pub fn new(tables: Vec<DenseMultilinearExtension<F>>) -> Self {
+        assert!(!tables.is_empty(), "product needs at least one table");
+
+        let number_of_vars = tables[0].number_of_vars();
+        assert!(
+            tables.iter().all(|t| t.number_of_vars() == number_of_vars),
+            "all tables must have the same number of variables"
+        );
+
+        Self {
+            tables,
+            number_of_vars,
+        }
+    }
+}
+
+impl<F: Field> Prover<F> for ProductSumcheck<F> {
+    fn claimed_sum(&self) -> F {
+        (0..self.tables[0].evaluations.len()).fold(F::zero(), |acc, i| {
+            acc + self
+                .tables
+                .iter()
+                .fold(F::one(), |product, table| product * table.evaluations[i])
+        })
+    }
+
+    fn number_of_vars(&self) -> usize {
+        self.number_of_vars
+    }
+
+    fn degree(&self) -> usize {
+        self.tables.len()
+    }
+
+    fn round_evaluations(&mut self, challenge: Option<F>) -> Option<Vec<F>> {
+        if let Some(challenge) = challenge {
+            for table in &mut self.tables {
+                table.fix_first_variable(challenge);
+            }
+        }
+
+        if self.tables[0].number_of_vars() == 0 {
+            return None;
+        }
+
+        let degree = self.degree();
+        let mut evaluations = vec![F::zero(); degree + 1];
+        let mut products = vec![F::one(); degree + 1];
+
+        for i in 0..self.tables[0].evaluations.len() / 2 {
+            products.fill(F::one());
+
+            for table in &self.tables {
+                let mut value = table.evaluations[2 * i];
+                let step = table.evaluations[2 * i + 1] - value;
+
+                for product in products.iter_mut() {
+                    *product = *product * value;
+                    value = value + step;
+                }
+            }
+
+            for (evaluation, product) in evaluations.iter_mut().zip(&products) {
+                *evaluation = *evaluation + *product;
+            }
+        }
+
+        Some(evaluations)
+    }
+
+    fn final_evaluation(&self) -> F {
+        self.tables
+            .iter()
+            .fold(F::one(), |product, table| product * table.evaluations[0])
+    }
+}
+
+/// Prover for a `VirtualPolynomial`.
+///
+/// Every distinct table is evaluated at `0..=d` and folded once per round,
+/// however many products it appears in.
+pub struct VirtualPolynomialProver<F: Field = Fp> {
+    tables: Vec<DenseMultilinearExtension<F>>,
+    products: Vec<Product<F>>,
+    number_of_vars: usize,
+    degree: usize,
+}
+
+impl<F: Field> VirtualPolynomialProver<F> {
+    pub fn new(polynomial: VirtualPolynomial<F>) -> Self {
+        let number_of_vars = polynomial.number_of_vars();
+        let degree = polynomial.degree();
+        let (tables, products) = polynomial.into_parts();
+
+        Self {
+            tables,
+            products,
+            number_of_vars,
+            degree,
+        }
+    }
+}
+
+impl<F: Field> Prover<F> for VirtualPolynomialProver<F> {
+    fn claimed_sum(&self) -> F {
+        (0..1 << self.number_of_vars).fold(F::zero(), |acc, i| {
+            let values: Vec<F> = self.tables.iter().map(|t| t.evaluations[i]).collect();
+
+            acc + combine(&self.products, &values)
+        })
+    }
+
+    fn number_of_vars(&self) -> usize {
+        self.number_of_vars
+    }
+
+    fn degree(&self) -> usize {
+        self.degree
+    }
+
+    fn round_evaluations(&mut self, challenge: Option<F>) -> Option<Vec<F>> {
+        if let Some(challenge) = challenge {
+            for table in &mut self.tables {
+                table.fix_first_variable(challenge);
+            }
+
+            self.number_of_vars -= 1;
+        }
+
+        if self.number_of_vars == 0 {
+            return None;
+        }
+
+        let points = self.degree.max(1) + 1;
+        let mut evaluations = vec![F::zero(); points];
+
+        // values[t][i] is table i restricted to the current pair, at t
+        let mut values = vec![vec![F::zero(); self.tables.len()]; points];
+
+        for pair in 0..1 << (self.number_of_vars - 1) {
+            for (i, table) in self.tables.iter().enumerate() {
+                let mut value = table.evaluations[2 * pair];
+                let step = table.evaluations[2 * pair + 1] - value;
+
+                for at_t in values.iter_mut() {
+                    at_t[i] = value;
+                    value = value + step;
+                }
+            }
+
+            for (evaluation, at_t) in evaluations.iter_mut().zip(&values) {
+                *evaluation = *evaluation + combine(&self.products, at_t);
+            }
+        }
+
+        Some(evaluations)
+    }
+
+    fn final_evaluation(&self) -> F {
+        let values: Vec<F> = self.tables.iter().map(|t| t.evaluations[0]).collect();
+
+        combine(&self.products, &values)
+    }
+}
+
+/// Multilinear prover for a table over `B` with challenges and messages in `E`.
+///
+/// The first round is summed in the base field and the first challenge folds the
+/// table straight into `E`, every later round runs on the folded `MultilinearProver`.
+pub struct ExtensionMultilinearProver<B: Field, E: ExtensionField<B>> {
+    base: DenseMultilinearExtension<B>,
+    folded: Option<MultilinearProver<E>>,
+}
+
+impl<B: Field, E: ExtensionField<B>> ExtensionMultilinearProver<B, E> {
+    pub fn new(base: DenseMultilinearExtension<B>) -> Self {
+        Self { base, folded: None }
+    }
+}
+
+impl<B: Field, E: ExtensionField<B>> Prover<E> for ExtensionMultilinearProver<B, E> {
+    fn claimed_sum(&self) -> E {
+        E::from_base(self.base.sum_over_hyper_cube())
+    }
+
+    fn number_of_vars(&self) -> usize {
+        self.base.number_of_vars()
+    }
+
+    fn degree(&self) -> usize {
+        1
+    }
+
+    fn round_evaluations(&mut self, challenge: Option<E>) -> Option<Vec<E>> {
+        if let Some(folded) = &mut self.folded {
+            return folded.round_evaluations(challenge);
+        }
+
+        if let Some(challenge) = challenge {
+            let folded = MultilinearProver::new(self.base.fix_first_variable_into(challenge));
+
+            return self.folded.insert(folded).round_evaluations(None);
+        }
+
+        if self.base.number_of_vars() == 0 {
+            return None;
+        }
+
+        let evaluations = multilinear_round(&self.base.evaluations);
+
+        Some(evaluations.into_iter().map(E::from_base).collect())
+    }
+
+    fn final_evaluation(&self) -> E {
+        match &self.folded {
+            Some(folded) => folded.final_evaluation(),
+            None => E::from_base(self.base.evaluations[0]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::field::ExtensionField;
+    use crate::fp::Fp;
+    use crate::fp2::Fp2;
+    use crate::fp3::Fp3;
+    use crate::mle::DenseMultilinearExtension;
+    use crate::mpolynomial::MPolynomial;
+    use crate::pcs::{HashCommitment, PolynomialCommitmentScheme};
+    use crate::proof::prove;
+    use crate::prover::{
+        ExtensionMultilinearProver, MultilinearProver, ProductSumcheck, Prover, SumcheckProver,
+        VirtualPolynomialProver,
+    };
+    use crate::verifier::SumcheckVerifier;
+    use crate::virtual_polynomial::VirtualPolynomial;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_rounds() {
+        let polynomial = MPolynomial::from(vec![Fp(2), Fp(3), Fp(5), Fp(7)], vec![1, 1, 1]);
+        let mut prover = SumcheckProver::new(polynomial);
+
+        assert_eq!(prover.claimed_sum(), Fp(96));
+
+        assert!(prover.round(None).is_some());
+        assert!(prover.round(Some(Fp(2))).is_some());
+        assert!(prover.round(Some(Fp(3))).is_some());
+        assert!(prover.round(Some(Fp(4))).is_none());
+
+        assert_eq!(prover.final_evaluation(), Fp(2 * 2 + 3 * 3 + 5 * 4 + 7));
+    }
+
+    fn table(number_of_vars: usize) -> DenseMultilinearExtension {
+        DenseMultilinearExtension::from(
+            (0..1u64 << number_of_vars).map(|i| Fp(i * i + 3)).collect(),
+        )
+    }
+
+    /// Statement of a sumcheck over several tables, the concatenation of their commitments.
+    fn commit_all(tables: &[DenseMultilinearExtension]) -> Vec<u8> {
+        tables
+            .iter()
+            .flat_map(|table| HashCommitment.commit(table).0)
+            .collect()
+    }
+
+    #[test]
+    fn test_multilinear_prover_matches_generic_prover() {
+        let polynomial = MPolynomial::from(vec![Fp(2), Fp(3), Fp(5), Fp(7)], vec![1, 1, 1]);
+        let evaluations = (0..8u64)
+            .map(|i| polynomial.eval(&[Fp(i & 1), Fp((i >> 1) & 1), Fp((i >> 2) & 1)]))
+            .collect();
+
+        let mut generic = SumcheckProver::new(polynomial);
+        let mut multilinear = MultilinearProver::new(DenseMultilinearExtension::from(evaluations));
+
+        assert_eq!(generic.claimed_sum(), multilinear.claimed_sum());
+
+        let mut challenge = None;
+        for r in [Fp(5), Fp(8), Fp(13)] {
+            let expected = generic.round(challenge).unwrap();
+            let received = multilinear.round(challenge).unwrap();
+
+            assert_eq!(received.coefficients, expected.coefficients);
+            challenge = Some(r);
+        }
+
+        assert!(generic.round(challenge).is_none());
+        assert!(multilinear.round(challenge).is_none());
+        assert_eq!(multilinear.final_evaluation(), generic.final_evaluation());
+    }
+
+    #[test]
+    fn test_multilinear_proof() {
+        let table = table(10);
+        let claim = table.sum_over_hyper_cube();
+
+        let (commitment, _) = HashCommitment.commit(&table);
+
+        let proof = prove(MultilinearProver::new(table.clone()), &commitment);
+        let verifier = SumcheckVerifier::new(claim, 10, 1, |x| table.eval(x));
+
+        let randomness = verifier.verify_proof(&proof, &commitment).unwrap();
+        assert_eq!(proof.final_evaluation, table.eval(&randomness));
+    }
+
+    #[test]
+    fn test_extension_multilinear_proof() {
+        let table = table(8);
+        let claim = Fp3::from_base(table.sum_over_hyper_cube());
+
+        let (commitment, _) = HashCommitment.commit(&table);
+
+        let lifted = table.lift::<Fp3>();
+        let proof = prove(ExtensionMultilinearProver::new(table), &commitment);
+        let verifier = SumcheckVerifier::new(claim, 8, 1, |x| lifted.eval(x));
+
+        let randomness = verifier.verify_proof(&proof, &commitment).unwrap();
+        assert_eq!(proof.final_evaluation, lifted.eval(&randomness));
+        assert_eq!(
+            proof.to_bytes(),
+            prove(MultilinearProver::new(lifted.clone()), &commitment).to_bytes()
+        );
+    }
+
+    #[test]
+    fn test_base_polynomial_with_extension_challenges() {
+        let polynomial = MPolynomial::from(vec![Fp(2), Fp(3), Fp(5), Fp(7)], vec![3, 2, 5]);
+        let lifted = polynomial.lift::<Fp2>();
+
+        let proof = prove(SumcheckProver::new(lifted.clone()), &lifted.to_bytes());
+        assert_eq!(
+            proof.claimed_sum,
+            Fp2::from_base(polynomial.sum_over_hyper_cube(None))
+        );
+
+        let verifier = SumcheckVerifier::new(proof.claimed_sum, 3, 5, |x| lifted.eval(x));
+        assert!(verifier.verify_proof(&proof, &lifted.to_bytes()).is_ok());
+    }
+
+    #[test]
+    fn test_product_sumcheck_matches_multilinear_prover() {
+        let mut single = ProductSumcheck::new(vec![table(4)]);
+        let mut multilinear = MultilinearProver::new(table(4));
+
+        assert_eq!(single.claimed_sum(), multilinear.claimed_sum());
+
+        let mut challenge = None;
+        for r in [Fp(3), Fp(5), Fp(7), Fp(9)] {
+            assert_eq!(
+                single.round_evaluations(challenge),
+                multilinear.round_evaluations(challenge)
+            );
+            challenge = Some(r);
+        }
+    }
+
+    #[test]
+    fn test_product_sumcheck() {
+        let tables: Vec<_> = (0..3)
+            .map(|k| {
+                DenseMultilinearExtension::from(
+                    (0..1u64 << 6).map(|i| Fp(i * i + k * i + 1)).collect(),
+                )
+            })
+            .collect();
+
+        let expected = (0..1 << 6).fold(Fp(0), |acc, i| {
+            acc + tables[0].evaluations[i] * tables[1].evaluations[i] * tables[2].evaluations[i]
+        });
+
+        let prover = ProductSumcheck::new(tables.clone());
+        assert_eq!(prover.claimed_sum(), expected);
+        assert_eq!(prover.degree(), 3);
+
+        let statement = commit_all(&tables);
+        let proof = prove(prover, &statement);
+        let oracle = |x: &[Fp]| tables.iter().fold(Fp(1), |acc, t| acc * t.eval(x));
+
+        let randomness = SumcheckVerifier::new(expected, 6, 3, oracle)
+            .verify_proof(&proof, &statement)
+            .unwrap();
+        assert_eq!(proof.final_evaluation, oracle(&randomness));
+
+        // Degree two messages cannot describe a degree three round
+        assert!(SumcheckVerifier::new(expected, 6, 2, oracle)
+            .verify_proof(&proof, &statement)
+            .is_err());
+    }
+
+    #[test]
+    fn test_virtual_polynomial_matches_product_sumcheck() {
+        let tables: Vec<_> = (1..4)
+            .map(|k| DenseMultilinearExtension::from((0..8).map(|i| Fp(k * i + 1)).collect()))
+            .collect();
+
+        let mut polynomial = VirtualPolynomial::new(tables[0].number_of_vars());
+        polynomial.add_product(Fp(1), tables.iter().cloned().map(Rc::new).collect());
+
+        let mut product = ProductSumcheck::new(tables.clone());
+        let mut virtual_prover = VirtualPolynomialProver::new(polynomial);
+
+        assert_eq!(virtual_prover.claimed_sum(), product.claimed_sum());
+
+        let mut challenge = None;
+        for r in [Fp(3), Fp(5), Fp(7)] {
+            assert_eq!(
+                virtual_prover.round_evaluations(challenge),
+                product.round_evaluations(challenge)
+            );
+            challenge = Some(r);
+        }
+    }
+
+    #[test]
+    fn test_virtual_polynomial_proof() {
+        let f1 = Rc::new(table(5));
+        let f2 = Rc::new(DenseMultilinearExtension::from(
+            f1.evaluations.iter().map(|x| *x + Fp(9)).collect(),
+        ));
+        let f3 = Rc::new(DenseMultilinearExtension::from(
+            f1.evaluations.iter().map(|x| *x * *x).collect(),
+        ));
+
+        // f1 * f2 + 3 * f2 * f3 * f3 + 5 * f1
+        let mut polynomial = VirtualPolynomial::new(5);
+        polynomial.add_product(Fp(1), vec![f1.clone(), f2.clone()]);
+        polynomial.add_product(Fp(3), vec![f2.clone(), f3.clone(), f3.clone()]);
+        polynomial.add_product(Fp(5), vec![f1.clone()]);
+
+        let claim = polynomial.sum_over_hyper_cube();
+        let oracle = polynomial.clone();
+
+        let prover = VirtualPolynomialProver::new(polynomial);
+        assert_eq!(prover.claimed_sum(), claim);
+        assert_eq!(prover.degree(), 3);
+
+        let statement = commit_all(&[(*f1).clone(), (*f2).clone(), (*f3).clone()]);
+        let proof = prove(prover, &statement);
+        let randomness = SumcheckVerifier::new(claim, 5, 3, |x| oracle.eval(x))
+            .verify_proof(&proof, &statement)
+            .unwrap();
+
+        assert_eq!(proof.final_evaluation, oracle.eval(&randomness));
+    }
+}
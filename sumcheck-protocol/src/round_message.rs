@@ -0,0 +1,136 @@
+use crate::error::SumcheckError;
+use crate::field::Field;
+use crate::fp::Fp;
+use crate::interpolation::InterpolationDomain;
+use crate::upolynomial::UPolynomial;
+
+/// Compressed round polynomial `g` of degree `d`: the evaluations `g(0), g(2), ..., g(d)`.
+///
+/// `g(1)` is not sent, the verifier recovers it as `claim - g(0)` from the value it
+/// expects `g(0) + g(1)` to have.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RoundMessage<F: Field = Fp> {
+    pub evaluations: Vec<F>,
+}
+
+impl<F: Field> RoundMessage<F> {
+    /// Compresses `g(0), g(1), ..., g(d)`.
+    pub fn from_evaluations(evaluations: &[F]) -> Self {
+        assert!(
+            !evaluations.is_empty(),
+            "round polynomial needs an evaluation"
+        );
+
+        let mut compressed = vec![evaluations[0]];
+        compressed.extend_from_slice(evaluations.get(2..).unwrap_or(&[]));
+
+        Self {
+            evaluations: compressed,
+        }
+    }
+
+    pub fn from_polynomial(polynomial: &UPolynomial<F>) -> Self {
+        let evaluations: Vec<F> = (0..=round_polynomial_degree(polynomial) as u64)
+            .map(|x| polynomial.evaluate(F::from_u64(x)))
+            .collect();
+
+        Self::from_evaluations(&evaluations)
+    }
+
+    /// Degree of the round polynomial, a degree 0 message is sent as a degree 1 one.
+    pub fn degree(&self) -> usize {
+        self.evaluations.len()
+    }
+
+    /// Restores `g(0), g(1), ..., g(d)` given the claimed `g(0) + g(1)`.
+    ///
+    /// A message without any evaluation, e.g. a decoded one, carries no round polynomial.
+    pub fn decompress(&self, claim: F) -> Result<Vec<F>, SumcheckError> {
+        let (first, rest) = self
+            .evaluations
+            .split_first()
+            .ok_or(SumcheckError::MissingMessage)?;
+
+        let mut evaluations = vec![*first, claim - *first];
+        evaluations.extend_from_slice(rest);
+
+        Ok(evaluations)
+    }
+
+    /// Evaluates the round polynomial at `x` without recovering its coefficients.
+    pub fn evaluate(&self, claim: F, x: F) -> Result<F, SumcheckError> {
+        let evaluations = self.decompress(claim)?;
+
+        Ok(InterpolationDomain::new(self.degree()).evaluate(&evaluations, x))
+    }
+}
+
+/// Degree of a received round polynomial, zero coefficients of the highest degrees
+/// are not counted and the zero polynomial, possibly empty, has degree 0.
+pub(crate) fn round_polynomial_degree<F: Field>(polynomial: &UPolynomial<F>) -> usize {
+    polynomial.degree().unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::SumcheckError;
+    use crate::fp::Fp;
+    use crate::round_message::{round_polynomial_degree, RoundMessage};
+    use crate::upolynomial::UPolynomial;
+
+    #[test]
+    fn test_compress_and_decompress() {
+        let evaluations = vec![Fp(7), Fp(14), Fp(33), Fp(76)];
+        let message = RoundMessage::from_evaluations(&evaluations);
+
+        assert_eq!(message.evaluations, vec![Fp(7), Fp(33), Fp(76)]);
+        assert_eq!(message.degree(), 3);
+        assert_eq!(message.decompress(Fp(21)), Ok(evaluations));
+    }
+
+    #[test]
+    fn test_from_polynomial() {
+        let polynomial = UPolynomial::from_coeffs_be(vec![Fp(2), Fp(0), Fp(5), Fp(7)]);
+        let message = RoundMessage::from_polynomial(&polynomial);
+
+        assert_eq!(message.evaluations, vec![Fp(7), Fp(33), Fp(76)]);
+        assert_eq!(message.evaluate(Fp(21), Fp(10)), Ok(Fp(2057)));
+    }
+
+    #[test]
+    fn test_constant_round() {
+        let message = RoundMessage::from_evaluations(&[Fp(4)]);
+
+        assert_eq!(message.degree(), 1);
+        assert_eq!(message.evaluate(Fp(8), Fp(123)), Ok(Fp(4)));
+    }
+
+    #[test]
+    fn test_empty_message() {
+        let message = RoundMessage::<Fp> {
+            evaluations: vec![],
+        };
+
+        assert_eq!(
+            message.decompress(Fp(8)),
+            Err(SumcheckError::MissingMessage)
+        );
+        assert_eq!(
+            message.evaluate(Fp(8), Fp(3)),
+            Err(SumcheckError::MissingMessage)
+        );
+    }
+
+    #[test]
+    fn test_round_polynomial_degree_ignores_zero_top_coefficients() {
+        let line = UPolynomial::from(vec![Fp(3), Fp(2), Fp(0), Fp(0)]);
+
+        assert_eq!(round_polynomial_degree(&line), 1);
+        assert_eq!(
+            RoundMessage::from_polynomial(&line).evaluations,
+            vec![Fp(3)]
+        );
+        assert_eq!(round_polynomial_degree(&UPolynomial::<Fp>::zero()), 0);
+        assert_eq!(round_polynomial_degree(&UPolynomial::from(vec![Fp(0)])), 0);
+    }
+}
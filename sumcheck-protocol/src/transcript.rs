@@ -0,0 +1,185 @@
+use crate::field::Field;
+use crate::round_message::RoundMessage;
+use crate::upolynomial::UPolynomial;
+use sha2::{Digest, Sha256};
+
+/// Fiat-Shamir transcript based on SHA-256.
+///
+/// Every absorbed message and every squeezed challenge is chained into a
+/// 32-byte state, so a challenge depends on everything sent before it.
+#[derive(Debug, Clone)]
+pub struct Transcript {
+    state: [u8; 32],
+}
+
+impl Transcript {
+    pub fn new(label: &[u8]) -> Self {
+        let mut transcript = Self { state: [0; 32] };
+        transcript.absorb_bytes(b"transcript", label);
+
+        transcript
+    }
+
+    pub fn absorb_bytes(&mut self, label: &[u8], bytes: &[u8]) {
+        let mut hasher = Sha256::new();
+
+        hasher.update(self.state);
+        hasher.update((label.len() as u64).to_le_bytes());
+        hasher.update(label);
+        hasher.update((bytes.len() as u64).to_le_bytes());
+        hasher.update(bytes);
+
+        self.state = hasher.finalize().into();
+    }
+
+    pub fn absorb_u64(&mut self, label: &[u8], value: u64) {
+        self.absorb_bytes(label, &value.to_le_bytes());
+    }
+
+    pub fn absorb_field<F: Field>(&mut self, label: &[u8], value: &F) {
+        self.absorb_bytes(label, &value.to_bytes());
+    }
+
+    pub fn absorb_fields<F: Field>(&mut self, label: &[u8], values: &[F]) {
+        let mut bytes = Vec::with_capacity(values.len() * F::NUM_BYTES);
+
+        for value in values {
+            bytes.extend_from_slice(&value.to_bytes());
+        }
+
+        self.absorb_bytes(label, &bytes);
+    }
+
+    pub fn absorb_polynomial<F: Field>(&mut self, label: &[u8], polynomial: &UPolynomial<F>) {
+        self.absorb_fields(label, &polynomial.coefficients);
+    }
+
+    pub fn absorb_round_message<F: Field>(&mut self, label: &[u8], message: &RoundMessage<F>) {
+        self.absorb_fields(label, &message.evaluations);
+    }
+
+    /// Squeezes a uniformly distributed field element.
+    /// Candidates that are not a canonical encoding are rejected instead of reduced to avoid bias.
+    pub fn challenge<F: Field>(&mut self, label: &[u8]) -> F {
+        let mut counter: u64 = 0;
+
+        loop {
+            let mut candidate = vec![];
+            let mut block: u64 = 0;
+
+            while candidate.len() < F::NUM_BYTES {
+                let mut hasher = Sha256::new();
+
+                hasher.update(self.state);
+                hasher.update((label.len() as u64).to_le_bytes());
+                hasher.update(label);
+                hasher.update(counter.to_le_bytes());
+                hasher.update(block.to_le_bytes());
+
+                candidate.extend_from_slice(&hasher.finalize());
+                block += 1;
+            }
+
+            candidate.truncate(F::NUM_BYTES);
+
+            if let Some(challenge) = F::from_bytes(&candidate) {
+                self.absorb_bytes(label, &candidate);
+                return challenge;
+            }
+
+            counter += 1;
+        }
+    }
+
+    /// Squeezes a uniformly distributed index in `0..bound`, rejecting like `challenge`.
+    pub fn challenge_index(&mut self, label: &[u8], bound: usize) -> usize {
+        assert!(bound > 0);
+
+        let bound = bound as u64;
+        let zone = u64::MAX - u64::MAX % bound;
+        let mut counter: u64 = 0;
+
+        loop {
+            let mut hasher = Sha256::new();
+
+            hasher.update(self.state);
+            hasher.update((label.len() as u64).to_le_bytes());
+            hasher.update(label);
+            hasher.update(counter.to_le_bytes());
+
+            let candidate: [u8; 8] = hasher.finalize()[..8].try_into().unwrap();
+            let value = u64::from_le_bytes(candidate);
+
+            if value < zone {
+                self.absorb_bytes(label, &candidate);
+                return (value % bound) as usize;
+            }
+
+            counter += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::babybear::BabyBear;
+    use crate::fp::Fp;
+    use crate::transcript::Transcript;
+
+    #[test]
+    fn test_challenge_is_deterministic() {
+        let mut first = Transcript::new(b"test");
+        let mut second = Transcript::new(b"test");
+
+        first.absorb_field(b"value", &Fp(42));
+        second.absorb_field(b"value", &Fp(42));
+
+        for _ in 0..2 {
+            let expected: Fp = first.challenge(b"challenge");
+            assert_eq!(second.challenge::<Fp>(b"challenge"), expected);
+        }
+    }
+
+    #[test]
+    fn test_challenge_depends_on_messages() {
+        let mut first = Transcript::new(b"test");
+        let mut second = Transcript::new(b"test");
+
+        first.absorb_field(b"value", &Fp(42));
+        second.absorb_field(b"value", &Fp(43));
+
+        let first: Fp = first.challenge(b"challenge");
+        assert_ne!(second.challenge::<Fp>(b"challenge"), first);
+    }
+
+    #[test]
+    fn test_consecutive_challenges_differ() {
+        let mut transcript = Transcript::new(b"test");
+
+        let first: Fp = transcript.challenge(b"challenge");
+        let second: Fp = transcript.challenge(b"challenge");
+
+        assert_ne!(first, second);
+    }
+
+    #[test]
+    fn test_challenge_index() {
+        let mut transcript = Transcript::new(b"test");
+        let indices: Vec<usize> = (0..100)
+            .map(|_| transcript.challenge_index(b"index", 6))
+            .collect();
+
+        assert!(indices.iter().all(|index| *index < 6));
+        assert!((0..6).all(|i| indices.contains(&i)));
+    }
+
+    #[test]
+    fn test_challenge_in_other_field() {
+        let mut transcript = Transcript::new(b"test");
+
+        for _ in 0..100 {
+            let challenge: BabyBear = transcript.challenge(b"challenge");
+            assert!(challenge.value() < BabyBear::MODULO);
+        }
+    }
+}
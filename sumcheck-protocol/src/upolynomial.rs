@@ -1,21 +1,55 @@
+use crate::error::InterpolationError;
+use crate::field::Field;
 use crate::fp::Fp;
-use std::ops::Div;
-
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+
+/// Number of points from which `evaluate_many` builds a subproduct tree.
+const MULTIPOINT_THRESHOLD: usize = 64;
+
+/// Size of the quotient and of the divisor from which `div_rem` inverts a power series.
+const FAST_DIVISION_THRESHOLD: usize = 64;
+
+/// Univariate polynomial `sum_i coefficients[i] * x^i`.
+///
+/// Coefficients are little-endian, lowest degree first, everywhere in this type:
+/// constructors, evaluation, interpolation and arithmetic. `from_coeffs_be` reads
+/// the usual written order, highest degree first.
+///
+/// An empty coefficient vector is the zero polynomial. Arithmetic drops zero
+/// coefficients of the highest degrees from its results, see `normalize`.
 #[derive(Debug, Clone)]
-pub struct UPolynomial {
-    pub coefficients: Vec<Fp>,
+pub struct UPolynomial<F: Field = Fp> {
+    pub coefficients: Vec<F>,
 }
 
-impl UPolynomial {
-    pub fn from(coefficients: Vec<Fp>) -> Self {
+impl<F: Field> UPolynomial<F> {
+    /// Same as `from_coeffs_le`.
+    pub fn from(coefficients: Vec<F>) -> Self {
         Self { coefficients }
     }
 
-    pub fn zero_at_given_x(xs: &Vec<Fp>) -> Self {
-        let mut root = vec![Fp::one()];
+    /// `coefficients[i]` is the coefficient of `x^i`.
+    pub fn from_coeffs_le(coefficients: Vec<F>) -> Self {
+        Self::from(coefficients)
+    }
+
+    /// `coefficients[0]` is the coefficient of the highest degree, as polynomials are written.
+    pub fn from_coeffs_be(mut coefficients: Vec<F>) -> Self {
+        coefficients.reverse();
+
+        Self::from(coefficients)
+    }
+
+    pub fn zero() -> Self {
+        Self::from(vec![])
+    }
+
+    /// `prod_i (x - xs[i])`
+    pub fn zero_at_given_x(xs: &[F]) -> Self {
+        let mut root = vec![F::one()];
 
         for x in xs {
-            root.insert(0, Fp::zero());
+            root.insert(0, F::zero());
 
             for j in 0..root.len() - 1 {
                 root[j] = root[j] - (root[j + 1] * *x)
@@ -25,134 +59,711 @@ impl UPolynomial {
         UPolynomial::from(root)
     }
 
-    pub fn interpolate(points: Vec<(Fp, Fp)>) -> Self {
+    /// Lagrange interpolation through `points`, with `points.len()` coefficients.
+    ///
+    /// Fails on duplicate x-coordinates instead of letting `multi_inv` skip the
+    /// zero denominator they produce.
+    pub fn interpolate(points: Vec<(F, F)>) -> Result<Self, InterpolationError> {
+        if points.is_empty() {
+            return Err(InterpolationError::EmptyInput);
+        }
+
         let (xs, ys) = &points
             .iter()
-            .map(|(x, y)| (x.clone(), y.clone()))
-            .unzip::<Fp, Fp, Vec<Fp>, Vec<Fp>>();
+            .map(|(x, y)| (*x, *y))
+            .unzip::<F, F, Vec<F>, Vec<F>>();
 
-        let mut root = UPolynomial::zero_at_given_x(xs);
-
-        if root.coefficients.len() != points.len() + 1 {
-            return UPolynomial::from(vec![]);
-        }
+        let root = UPolynomial::zero_at_given_x(xs);
 
         let mut numerators = vec![];
         for x in xs {
-            numerators.push(root.clone() / UPolynomial::from(vec![Fp::neg(x), Fp::one()]))
+            numerators.push(root.clone() / UPolynomial::from(vec![-*x, F::one()]))
         }
 
         let mut denominator = vec![];
         for i in 0..xs.len() {
-            denominator.push(numerators[i].eval(&[xs[i]]))
+            denominator.push(numerators[i].evaluate(xs[i]))
+        }
+
+        // prod_{j != i} (x_i - x_j) is zero exactly when x_i appears twice
+        if let Some(first) = denominator.iter().position(|d| *d == F::zero()) {
+            let second = (first + 1..xs.len())
+                .find(|j| xs[*j] == xs[first])
+                .expect("a zero denominator comes from a duplicate node");
+
+            return Err(InterpolationError::DuplicateNode { first, second });
         }
 
-        let inv_denominators = Fp::multi_inv(&denominator);
+        let inv_denominators = F::multi_inv(&denominator);
 
-        let mut b = vec![];
+        let mut b = vec![F::zero(); xs.len()];
         for i in 0..xs.len() {
             let y_slice = ys[i] * inv_denominators[i];
 
-            for j in 0..ys.len() {
-                if numerators[i].coefficients[j] != Fp::zero() && ys[i] != Fp::zero() {
-                    let res_mul = numerators[i].coefficients[j] * y_slice;
+            for (b_j, numerator_j) in b.iter_mut().zip(&numerators[i].coefficients) {
+                *b_j = *b_j + *numerator_j * y_slice;
+            }
+        }
 
-                    if b.len() <= j {
-                        b.push(Fp::zero());
-                    }
+        Ok(UPolynomial::from(b))
+    }
 
-                    b[j] = b[j] + res_mul;
-                }
-            }
+    /// Value at `x` by Horner's rule.
+    pub fn evaluate(&self, x: F) -> F {
+        self.coefficients
+            .iter()
+            .rev()
+            .fold(F::zero(), |acc, coefficient| acc * x + *coefficient)
+    }
+
+    /// Values at every point of `xs`.
+    ///
+    /// Many points go through a subproduct tree: the polynomial is reduced modulo
+    /// `prod (x - x_i)` over halves of the points, down to `f mod (x - x_i) = f(x_i)`.
+    pub fn evaluate_many(&self, xs: &[F]) -> Vec<F> {
+        if xs.len() < MULTIPOINT_THRESHOLD {
+            return xs.iter().map(|x| self.evaluate(*x)).collect();
+        }
+
+        // tree[0] are the linear factors, every level above multiplies pairs of nodes
+        let mut tree: Vec<Vec<Self>> = vec![xs
+            .iter()
+            .map(|x| UPolynomial::from(vec![-*x, F::one()]))
+            .collect()];
+
+        while tree.last().unwrap().len() > 1 {
+            let level = tree
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => left.clone() * right.clone(),
+                    _ => pair[0].clone(),
+                })
+                .collect();
+
+            tree.push(level);
+        }
+
+        let mut remainders = vec![self.clone()];
+        for level in tree.iter().rev() {
+            remainders = level
+                .iter()
+                .enumerate()
+                .map(|(i, node)| remainders[i / 2].clone() % node.clone())
+                .collect();
+        }
+
+        remainders
+            .iter()
+            .zip(xs)
+            .map(|(remainder, x)| remainder.evaluate(*x))
+            .collect()
+    }
+
+    /// Highest degree with a non-zero coefficient, `None` for the zero polynomial.
+    pub fn degree(&self) -> Option<usize> {
+        self.coefficients
+            .iter()
+            .rposition(|coefficient| *coefficient != F::zero())
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.coefficients
+            .iter()
+            .all(|coefficient| *coefficient == F::zero())
+    }
+
+    /// Drops the trailing zero coefficients, of the highest degrees. The zero
+    /// polynomial becomes empty.
+    pub fn normalize(&mut self) {
+        while self.coefficients.last() == Some(&F::zero()) {
+            self.coefficients.pop();
         }
+    }
+
+    fn normalized(mut self) -> Self {
+        self.normalize();
+        self
+    }
+
+    pub fn derivative(&self) -> Self {
+        let derivative = self
+            .coefficients
+            .iter()
+            .enumerate()
+            .skip(1)
+            .map(|(i, coefficient)| F::from_u64(i as u64) * *coefficient)
+            .collect();
 
-        UPolynomial::from(b)
+        Self::from(derivative).normalized()
     }
 
-    pub fn eval(&self, x: &[Fp]) -> Fp {
-        let coefficients_len = self.coefficients.len();
+    /// Quotient and remainder of the euclidean division by `divisor`.
+    pub fn div_rem(&self, divisor: &Self) -> (Self, Self) {
+        let divisor = &divisor.clone().normalized().coefficients;
+        assert!(!divisor.is_empty(), "division by the zero polynomial");
+
+        let mut remainder = self.clone().normalized().coefficients;
+        if remainder.len() < divisor.len() {
+            return (Self::zero(), Self::from(remainder));
+        }
+
+        let quotient_len = remainder.len() - divisor.len() + 1;
+
+        if quotient_len.min(divisor.len()) >= FAST_DIVISION_THRESHOLD {
+            // rev(q) = rev(a) / rev(b) mod x^quotient_len with the coefficients reversed,
+            // the leading coefficient of b becomes the constant term of rev(b)
+            let reversed_dividend: Vec<F> = remainder.iter().rev().copied().collect();
+            let reversed_divisor: Vec<F> = divisor.iter().rev().copied().collect();
+
+            let mut quotient = F::convolve(
+                &reversed_dividend[..quotient_len],
+                &inverse_series(&reversed_divisor, quotient_len),
+            );
+            quotient.truncate(quotient_len);
+            quotient.reverse();
+
+            let product = F::convolve(&quotient, divisor);
+            remainder.truncate(divisor.len() - 1);
+            for (r, p) in remainder.iter_mut().zip(product) {
+                *r -= p;
+            }
 
-        assert_eq!(x.len(), coefficients_len - 1);
+            return (
+                Self::from(quotient).normalized(),
+                Self::from(remainder).normalized(),
+            );
+        }
 
-        let mut result = Fp(0);
+        let lead_inverse = divisor[divisor.len() - 1].inverse();
+        let mut quotient = vec![F::zero(); quotient_len];
 
-        for i in 0..coefficients_len {
-            let mut term = self.coefficients[i];
+        for i in (0..quotient.len()).rev() {
+            let q = remainder[i + divisor.len() - 1] * lead_inverse;
+            quotient[i] = q;
 
-            if i < x.len() {
-                result = result + (term * x[i].pow(coefficients_len as u32 - 1 - i as u32))
-            } else {
-                result = result + term;
+            for (j, d_j) in divisor.iter().enumerate() {
+                remainder[i + j] -= *d_j * q;
             }
         }
 
-        result
+        remainder.truncate(divisor.len() - 1);
+
+        (
+            Self::from(quotient).normalized(),
+            Self::from(remainder).normalized(),
+        )
+    }
+}
+
+/// `1 / h mod x^len` by Newton iteration `g <- g * (2 - h * g)`, which doubles the
+/// number of correct coefficients every step. `h[0]` must be non-zero.
+fn inverse_series<F: Field>(h: &[F], len: usize) -> Vec<F> {
+    let mut g = vec![h[0].inverse()];
+
+    while g.len() < len {
+        let precision = (2 * g.len()).min(len);
+
+        let mut error = F::convolve(&h[..h.len().min(precision)], &g);
+        error.resize(precision, F::zero());
+        for coefficient in error.iter_mut() {
+            *coefficient = -*coefficient;
+        }
+        error[0] = error[0] + F::from_u64(2);
+
+        g = F::convolve(&g, &error);
+        g.truncate(precision);
     }
 
-    pub fn degree(&self) -> usize {
-        self.coefficients.len() - 1
+    g
+}
+
+/// Equal as polynomials, zero coefficients of the highest degrees are ignored.
+impl<F: Field> PartialEq for UPolynomial<F> {
+    fn eq(&self, other: &Self) -> bool {
+        self.clone().normalized().coefficients == other.clone().normalized().coefficients
     }
 }
 
-impl Div for UPolynomial {
+impl<F: Field> Add for UPolynomial<F> {
     type Output = Self;
 
-    fn div(self, rhs: Self) -> Self::Output {
-        let mut a_copy = self.coefficients.clone();
+    fn add(self, rhs: Self) -> Self::Output {
+        let (mut long, short) = if self.coefficients.len() >= rhs.coefficients.len() {
+            (self.coefficients, rhs.coefficients)
+        } else {
+            (rhs.coefficients, self.coefficients)
+        };
 
-        if a_copy.len() < rhs.coefficients.len() {
-            return UPolynomial::from(vec![]);
+        for (a, b) in long.iter_mut().zip(short) {
+            *a = *a + b;
         }
 
-        let mut output = vec![];
+        Self::from(long).normalized()
+    }
+}
+
+impl<F: Field> Neg for UPolynomial<F> {
+    type Output = Self;
 
-        let mut a_pos = a_copy.len() - 1;
-        let mut b_pos = rhs.coefficients.len() - 1;
-        let mut diff = a_pos - b_pos;
+    fn neg(mut self) -> Self::Output {
+        for coefficient in self.coefficients.iter_mut() {
+            *coefficient = -*coefficient;
+        }
 
-        loop {
-            let quot = a_copy[a_pos] / rhs.coefficients[b_pos];
-            output.push(quot);
+        self
+    }
+}
 
-            for i in (0..=b_pos).rev() {
-                a_copy[diff + i] = a_copy[diff + i] - (rhs.coefficients[i] * quot);
-            }
+impl<F: Field> Sub for UPolynomial<F> {
+    type Output = Self;
 
-            a_pos -= 1;
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + (-rhs)
+    }
+}
 
-            if diff == 0 {
-                break;
-            }
+/// Schoolbook or NTT product, whichever `Field::convolve` picks for the sizes.
+impl<F: Field> Mul for UPolynomial<F> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self::from(F::convolve(&self.coefficients, &rhs.coefficients)).normalized()
+    }
+}
+
+impl<F: Field> Mul<F> for UPolynomial<F> {
+    type Output = Self;
 
-            diff -= 1;
+    fn mul(mut self, rhs: F) -> Self::Output {
+        for coefficient in self.coefficients.iter_mut() {
+            *coefficient = *coefficient * rhs;
         }
 
-        UPolynomial::from(output)
+        self.normalized()
+    }
+}
+
+impl<F: Field> Div for UPolynomial<F> {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        self.div_rem(&rhs).0
+    }
+}
+
+impl<F: Field> Rem for UPolynomial<F> {
+    type Output = Self;
+
+    fn rem(self, rhs: Self) -> Self::Output {
+        self.div_rem(&rhs).1
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::error::InterpolationError;
+    use crate::field::{schoolbook_convolve, Field};
     use crate::fp::Fp;
     use crate::upolynomial::UPolynomial;
+    use proptest::prelude::*;
 
     #[test]
-    fn test_eval() {
-        let coefficients = vec![Fp(8), Fp(3), Fp(5)];
-        let polynomial = UPolynomial::from(coefficients);
+    fn test_constructors() {
+        // 8x^2 + 3x + 5
+        let le = UPolynomial::from_coeffs_le(vec![Fp(5), Fp(3), Fp(8)]);
+        let be = UPolynomial::from_coeffs_be(vec![Fp(8), Fp(3), Fp(5)]);
 
-        let x = vec![Fp(3), Fp(5)];
+        assert_eq!(le.coefficients, be.coefficients);
         assert_eq!(
-            polynomial.eval(&x),
-            Fp(8 * 3u64.pow(2) + 3 * 5u64.pow(1) + 5)
-        )
+            UPolynomial::from(vec![Fp(5), Fp(3), Fp(8)]).coefficients,
+            le.coefficients
+        );
+    }
+
+    #[test]
+    fn test_evaluate() {
+        // 8x^2 + 3x + 5
+        let polynomial = UPolynomial::from_coeffs_be(vec![Fp(8), Fp(3), Fp(5)]);
+
+        assert_eq!(polynomial.evaluate(Fp(3)), Fp(8 * 9 + 3 * 3 + 5));
+        assert_eq!(polynomial.evaluate(Fp(0)), Fp(5));
+        assert_eq!(UPolynomial::zero().evaluate(Fp(7)), Fp(0));
+    }
+
+    #[test]
+    fn test_evaluate_many() {
+        let polynomial = UPolynomial::from((0..200).map(|_| Fp::sample()).collect());
+
+        // Below and above the size of the subproduct tree
+        for len in [5, 150] {
+            let xs: Vec<Fp> = (0..len).map(|_| Fp::sample()).collect();
+            let expected: Vec<Fp> = xs.iter().map(|x| polynomial.evaluate(*x)).collect();
+
+            assert_eq!(polynomial.evaluate_many(&xs), expected);
+        }
+
+        let small = UPolynomial::from_coeffs_be(vec![Fp(2), Fp(1)]);
+        let xs: Vec<Fp> = (0..100).map(Fp).collect();
+        let expected: Vec<Fp> = (0..100).map(|x| Fp(2 * x + 1)).collect();
+        assert_eq!(small.evaluate_many(&xs), expected);
+    }
+
+    #[test]
+    fn test_zero_at_given_x() {
+        // (x - 2)(x - 3) = x^2 - 5x + 6
+        let polynomial = UPolynomial::zero_at_given_x(&[Fp(2), Fp(3)]);
+
+        assert_eq!(polynomial.coefficients, vec![Fp(6), -Fp(5), Fp(1)]);
     }
 
     #[test]
     fn test_interpolate() {
         let points = vec![(Fp(0), Fp(44)), (Fp(1), Fp(52))];
-        let polynomial = UPolynomial::interpolate(points);
+        let polynomial = UPolynomial::interpolate(points).unwrap();
+
+        assert_eq!(polynomial.coefficients, vec![Fp(44), Fp(8)])
+    }
 
-        assert_eq!(polynomial.coefficients, vec![Fp(8), Fp(44)])
+    #[test]
+    fn test_interpolate_quadratic() {
+        // x^2, the coefficient of x and the constant are both zero
+        let points = vec![(Fp(0), Fp(0)), (Fp(1), Fp(1)), (Fp(2), Fp(4))];
+        let polynomial = UPolynomial::interpolate(points).unwrap();
+
+        assert_eq!(polynomial.coefficients, vec![Fp(0), Fp(0), Fp(1)])
+    }
+
+    #[test]
+    fn test_interpolate_cubic() {
+        // 2x^3 + 5x + 7
+        let f = |x: u64| Fp(2 * x * x * x + 5 * x + 7);
+        let points = (0..4).map(|x| (Fp(x), f(x))).collect();
+        let polynomial = UPolynomial::interpolate(points).unwrap();
+
+        assert_eq!(polynomial.coefficients, vec![Fp(7), Fp(5), Fp(0), Fp(2)]);
+        assert_eq!(polynomial.evaluate(Fp(9)), f(9));
+    }
+
+    #[test]
+    fn test_mul() {
+        // (x + 2) * (3x^2 + 5) = 3x^3 + 6x^2 + 5x + 10
+        let a = UPolynomial::from_coeffs_be(vec![Fp(1), Fp(2)]);
+        let b = UPolynomial::from_coeffs_be(vec![Fp(3), Fp(0), Fp(5)]);
+
+        assert_eq!(
+            a * b,
+            UPolynomial::from_coeffs_be(vec![Fp(3), Fp(6), Fp(5), Fp(10)])
+        );
+    }
+
+    #[test]
+    fn test_mul_matches_schoolbook() {
+        // Large enough for `Fp::convolve` to go through the NTT
+        let a: Vec<Fp> = (0..130).map(|_| Fp::sample()).collect();
+        let b: Vec<Fp> = (0..70).map(|_| Fp::sample()).collect();
+
+        let expected = schoolbook_convolve(&a, &b);
+        let product = UPolynomial::from(a) * UPolynomial::from(b);
+
+        assert_eq!(product.coefficients, expected);
+    }
+
+    #[test]
+    fn test_add_and_sub() {
+        // (x^2 + 2x + 3) + (5x + 1) = x^2 + 7x + 4
+        let a = UPolynomial::from_coeffs_be(vec![Fp(1), Fp(2), Fp(3)]);
+        let b = UPolynomial::from_coeffs_be(vec![Fp(5), Fp(1)]);
+        let sum = UPolynomial::from_coeffs_be(vec![Fp(1), Fp(7), Fp(4)]);
+
+        assert_eq!(a.clone() + b.clone(), sum);
+        assert_eq!(b.clone() + a.clone(), sum);
+        assert_eq!(a.clone() - b.clone() + b, a);
+
+        // The leading terms cancel
+        let c = UPolynomial::from_coeffs_be(vec![Fp(1), Fp(0), Fp(9)]);
+        assert_eq!((a.clone() - c).coefficients, vec![-Fp(6), Fp(2)]);
+        assert!((a.clone() - a).is_zero());
+    }
+
+    #[test]
+    fn test_scalar_mul_and_neg() {
+        let a = UPolynomial::from(vec![Fp(1), Fp(2), Fp(3)]);
+
+        assert_eq!((a.clone() * Fp(3)).coefficients, vec![Fp(3), Fp(6), Fp(9)]);
+        assert_eq!(a.clone() * -Fp(1), -a.clone());
+        assert!((a * Fp(0)).coefficients.is_empty());
+    }
+
+    #[test]
+    fn test_normalize_and_eq() {
+        let mut a = UPolynomial::from(vec![Fp(0), Fp(4), Fp(0), Fp(0)]);
+
+        assert_eq!(a, UPolynomial::from(vec![Fp(0), Fp(4)]));
+        assert_ne!(a, UPolynomial::from(vec![Fp(4)]));
+
+        a.normalize();
+        assert_eq!(a.coefficients, vec![Fp(0), Fp(4)]);
+
+        let mut zero = UPolynomial::from(vec![Fp(0), Fp(0)]);
+        assert!(zero.is_zero());
+        assert_eq!(zero, UPolynomial::zero());
+
+        zero.normalize();
+        assert!(zero.coefficients.is_empty());
+    }
+
+    #[test]
+    fn test_div_rem() {
+        // x^3 + 2x^2 + 3x + 4 = (x^2 + 1) * (x + 2) + (2x + 2)
+        let a = UPolynomial::from_coeffs_be(vec![Fp(1), Fp(2), Fp(3), Fp(4)]);
+        let b = UPolynomial::from_coeffs_be(vec![Fp(1), Fp(0), Fp(1)]);
+
+        let (quotient, remainder) = a.div_rem(&b);
+        assert_eq!(quotient, UPolynomial::from_coeffs_be(vec![Fp(1), Fp(2)]));
+        assert_eq!(remainder, UPolynomial::from_coeffs_be(vec![Fp(2), Fp(2)]));
+
+        assert_eq!(a.clone() / b.clone(), quotient);
+        assert_eq!(a.clone() % b.clone(), remainder);
+        assert_eq!(quotient * b.clone() + remainder, a);
+
+        let (quotient, remainder) = b.div_rem(&a);
+        assert!(quotient.is_zero());
+        assert_eq!(remainder, b);
+    }
+
+    #[test]
+    fn test_div_rem_random() {
+        // The last sizes invert a power series instead of the long division
+        for (a_len, b_len) in [(20, 7), (300, 100), (200, 130)] {
+            let a = UPolynomial::from((0..a_len).map(|_| Fp::sample()).collect());
+            let b = UPolynomial::from((0..b_len).map(|_| Fp::sample()).collect());
+
+            let (quotient, remainder) = a.div_rem(&b);
+            assert!(remainder.coefficients.len() < b.coefficients.len());
+            assert_eq!(quotient * b + remainder, a);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "division by the zero polynomial")]
+    fn test_div_by_zero() {
+        let a = UPolynomial::from(vec![Fp(1), Fp(2)]);
+
+        let _ = a.div_rem(&UPolynomial::from(vec![Fp(0)]));
+    }
+
+    #[test]
+    fn test_interpolate_empty() {
+        assert_eq!(
+            UPolynomial::<Fp>::interpolate(vec![]).unwrap_err(),
+            InterpolationError::EmptyInput
+        );
+    }
+
+    #[test]
+    fn test_interpolate_duplicate_node() {
+        // Even a consistent repeated point leaves the system underdetermined
+        let points = vec![
+            (Fp(1), Fp(2)),
+            (Fp(5), Fp(3)),
+            (Fp(7), Fp(0)),
+            (Fp(5), Fp(3)),
+        ];
+
+        assert_eq!(
+            UPolynomial::interpolate(points).unwrap_err(),
+            InterpolationError::DuplicateNode {
+                first: 1,
+                second: 3
+            }
+        );
+    }
+
+    #[test]
+    fn test_degree() {
+        let p = UPolynomial::from(vec![Fp(3), Fp(0), Fp(5)]);
+
+        assert_eq!(p.degree(), Some(2));
+        assert_eq!((p.clone() - p).degree(), None);
+        assert_eq!(UPolynomial::<Fp>::zero().degree(), None);
+        assert_eq!(UPolynomial::from(vec![Fp(0), Fp(0)]).degree(), None);
+    }
+
+    #[test]
+    fn test_degree_ignores_zero_top_coefficients() {
+        let p = UPolynomial::from(vec![Fp(7), Fp(1), Fp(0), Fp(0)]);
+
+        assert_eq!(p.degree(), Some(1));
+        assert_eq!(p, UPolynomial::from(vec![Fp(7), Fp(1)]));
+        assert_eq!(UPolynomial::from(vec![Fp(7), Fp(0)]).degree(), Some(0));
+    }
+
+    #[test]
+    fn test_derivative() {
+        // 2x^3 + 5x + 7 -> 6x^2 + 5
+        let a = UPolynomial::from_coeffs_be(vec![Fp(2), Fp(0), Fp(5), Fp(7)]);
+
+        assert_eq!(
+            a.derivative(),
+            UPolynomial::from_coeffs_be(vec![Fp(6), Fp(0), Fp(5)])
+        );
+        assert!(UPolynomial::from(vec![Fp(7)]).derivative().is_zero());
+    }
+
+    fn fp() -> impl Strategy<Value = Fp> {
+        any::<u64>().prop_map(Fp::from)
+    }
+
+    fn polynomial(max_len: usize) -> impl Strategy<Value = UPolynomial> {
+        prop::collection::vec(fp(), 0..max_len).prop_map(UPolynomial::from)
+    }
+
+    fn distinct(max_len: usize) -> impl Strategy<Value = Vec<Fp>> {
+        prop::collection::btree_set(0..Fp::MAX.0, 1..max_len)
+            .prop_map(|xs| xs.into_iter().map(Fp).collect())
+    }
+
+    proptest! {
+        #[test]
+        fn prop_be_is_reversed_le(coefficients in prop::collection::vec(fp(), 0..20)) {
+            let mut reversed = coefficients.clone();
+            reversed.reverse();
+
+            prop_assert_eq!(
+                UPolynomial::from_coeffs_be(reversed).coefficients,
+                UPolynomial::from_coeffs_le(coefficients).coefficients
+            );
+        }
+
+        #[test]
+        fn prop_evaluate_is_sum_of_powers(p in polynomial(20), x in fp()) {
+            let expected = p
+                .coefficients
+                .iter()
+                .enumerate()
+                .fold(Fp(0), |acc, (i, c)| acc + *c * x.pow(i as u64));
+
+            prop_assert_eq!(p.evaluate(x), expected);
+        }
+
+        #[test]
+        fn prop_zero_at_given_x_vanishes(xs in prop::collection::vec(fp(), 0..10), x in fp()) {
+            let root = UPolynomial::zero_at_given_x(&xs);
+
+            prop_assert_eq!(root.coefficients.len(), xs.len() + 1);
+            prop_assert_eq!(root.coefficients.last(), Some(&Fp(1)));
+            for x_i in &xs {
+                prop_assert_eq!(root.evaluate(*x_i), Fp(0));
+            }
+
+            let expected = xs.iter().fold(Fp(1), |acc, x_i| acc * (x - *x_i));
+            prop_assert_eq!(root.evaluate(x), expected);
+        }
+
+        #[test]
+        fn prop_interpolate_recovers_polynomial(p in polynomial(12), xs in distinct(24)) {
+            prop_assume!(xs.len() >= p.coefficients.len());
+
+            let points = xs.iter().map(|x| (*x, p.evaluate(*x))).collect();
+            let interpolated = UPolynomial::interpolate(points).unwrap();
+
+            prop_assert_eq!(interpolated.coefficients.len(), xs.len());
+            prop_assert_eq!(interpolated, p);
+        }
+
+        #[test]
+        fn prop_interpolate_passes_through_points(xs in distinct(16), seed in fp()) {
+            let points: Vec<(Fp, Fp)> = xs.iter().map(|x| (*x, *x * *x + seed)).collect();
+            let polynomial = UPolynomial::interpolate(points.clone()).unwrap();
+
+            for (x, y) in points {
+                prop_assert_eq!(polynomial.evaluate(x), y);
+            }
+        }
+
+        #[test]
+        fn prop_interpolate_any_nodes(xs in prop::collection::vec(0..8u64, 0..12), seed in fp()) {
+            // Few distinct values, so duplicates are common
+            let points: Vec<(Fp, Fp)> = xs.iter().map(|x| (Fp(*x), Fp(*x) * seed)).collect();
+
+            match UPolynomial::interpolate(points.clone()) {
+                Ok(polynomial) => {
+                    prop_assert_eq!(polynomial.coefficients.len(), points.len());
+                    for (x, y) in points {
+                        prop_assert_eq!(polynomial.evaluate(x), y);
+                    }
+                }
+                Err(InterpolationError::EmptyInput) => prop_assert!(xs.is_empty()),
+                Err(InterpolationError::DuplicateNode { first, second }) => {
+                    prop_assert!(first < second);
+                    prop_assert_eq!(xs[first], xs[second]);
+                    prop_assert!(xs[..first].iter().all(|x| xs.iter().filter(|y| *y == x).count() == 1));
+                }
+            }
+
+            let mut unique = xs.clone();
+            unique.sort();
+            unique.dedup();
+            prop_assert_eq!(UPolynomial::interpolate(
+                xs.iter().map(|x| (Fp(*x), seed)).collect()
+            ).is_ok(), !xs.is_empty() && unique.len() == xs.len());
+        }
+
+        #[test]
+        fn prop_ring_operations_match_evaluate(a in polynomial(100), b in polynomial(100), x in fp(), c in fp()) {
+            prop_assert_eq!((a.clone() + b.clone()).evaluate(x), a.evaluate(x) + b.evaluate(x));
+            prop_assert_eq!((a.clone() - b.clone()).evaluate(x), a.evaluate(x) - b.evaluate(x));
+            prop_assert_eq!((a.clone() * b.clone()).evaluate(x), a.evaluate(x) * b.evaluate(x));
+            prop_assert_eq!((a.clone() * c).evaluate(x), a.evaluate(x) * c);
+            prop_assert_eq!((-a.clone()).evaluate(x), -a.evaluate(x));
+        }
+
+        #[test]
+        fn prop_mul_matches_schoolbook(a in polynomial(100), b in polynomial(100)) {
+            let expected = UPolynomial::from(schoolbook_convolve(&a.coefficients, &b.coefficients));
+
+            prop_assert_eq!(a * b, expected);
+        }
+
+        #[test]
+        fn prop_div_rem(a in polynomial(40), b in polynomial(20), x in fp()) {
+            prop_assume!(!b.is_zero());
+
+            let (quotient, remainder) = a.div_rem(&b);
+
+            let mut normalized = b.clone();
+            normalized.normalize();
+            prop_assert!(remainder.coefficients.len() < normalized.coefficients.len());
+            prop_assert_eq!(
+                a.evaluate(x),
+                quotient.evaluate(x) * b.evaluate(x) + remainder.evaluate(x)
+            );
+        }
+
+        #[test]
+        fn prop_div_by_vanishing_polynomial(p in polynomial(30), xs in distinct(10)) {
+            // p - I(p) vanishes on xs, so the remainder by prod (x - x_i) is zero
+            let points = xs.iter().map(|x| (*x, p.evaluate(*x))).collect();
+            let difference = p - UPolynomial::interpolate(points).unwrap();
+
+            prop_assert!((difference % UPolynomial::zero_at_given_x(&xs)).is_zero());
+        }
+
+        #[test]
+        fn prop_derivative_product_rule(a in polynomial(20), b in polynomial(20)) {
+            prop_assert_eq!(
+                (a.clone() * b.clone()).derivative(),
+                a.derivative() * b.clone() + a * b.derivative()
+            );
+        }
+
+        #[test]
+        fn prop_evaluate_many(p in polynomial(100), xs in prop::collection::vec(fp(), 0..100)) {
+            let expected: Vec<Fp> = xs.iter().map(|x| p.evaluate(*x)).collect();
+
+            prop_assert_eq!(p.evaluate_many(&xs), expected);
+        }
     }
 }
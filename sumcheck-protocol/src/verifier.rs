@@ -0,0 +1,330 @@
+use crate::error::SumcheckError;
+use crate::field::Field;
+use crate::fp::Fp;
+use crate::interpolation::InterpolationDomain;
+use crate::proof::{new_transcript, SumcheckProof};
+use crate::round_message::{round_polynomial_degree, RoundMessage};
+use crate::transcript::Transcript;
+use crate::upolynomial::UPolynomial;
+use crate::VerifierState;
+
+/// Evaluates the summed polynomial at a point, e.g. by opening a commitment.
+pub type Oracle<'a, F = Fp> = Box<dyn Fn(&[F]) -> F + 'a>;
+
+/// Verifier side of the sumcheck protocol.
+///
+/// The verifier only knows the claim, the shape of the polynomial and an oracle
+/// that is queried once, at the very end, for the evaluation at the random point.
+pub struct SumcheckVerifier<'a, F: Field = Fp> {
+    claim: F,
+    number_of_vars: usize,
+    max_degree: usize,
+    domain: InterpolationDomain<F>,
+    oracle: Oracle<'a, F>,
+    expected: F,
+    randomness: Vec<F>,
+    accepted: bool,
+}
+
+impl<'a, F: Field> SumcheckVerifier<'a, F> {
+    pub fn new(
+        claim: F,
+        number_of_vars: usize,
+        max_degree: usize,
+        oracle: impl Fn(&[F]) -> F + 'a,
+    ) -> Self {
+        Self {
+            claim,
+            number_of_vars,
+            max_degree,
+            // Degree 0 rounds are sent as degree 1 messages
+            domain: InterpolationDomain::new(max_degree.max(1)),
+            oracle: Box::new(oracle),
+            expected: claim,
+            randomness: vec![],
+            accepted: false,
+        }
+    }
+
+    /// Checks the round polynomial and answers with a freshly sampled challenge.
+    pub fn receive(
+        &mut self,
+        message: Option<UPolynomial<F>>,
+    ) -> Result<VerifierState<F>, SumcheckError> {
+        self.receive_with_challenge(message, F::sample())
+    }
+
+    /// Same as `receive`, but the challenge is supplied by the caller, e.g. from a `Transcript`.
+    pub fn receive_with_challenge(
+        &mut self,
+        message: Option<UPolynomial<F>>,
+        challenge: F,
+    ) -> Result<VerifierState<F>, SumcheckError> {
+        if self.accepted || self.randomness.len() >= self.number_of_vars {
+            return Err(SumcheckError::ProtocolFinished);
+        }
+
+        let round_polynomial = message.ok_or(SumcheckError::MissingMessage)?;
+        let round = self.randomness.len();
+
+        let degree = round_polynomial_degree(&round_polynomial);
+        if degree > self.max_degree {
+            return Err(SumcheckError::DegreeTooHigh {
+                round,
+                degree,
+                max_degree: self.max_degree,
+            });
+        }
+
+        let sum = round_polynomial.evaluate(F::zero()) + round_polynomial.evaluate(F::one());
+        if sum != self.expected {
+            return Err(SumcheckError::RoundSumMismatch { round });
+        }
+
+        let expected = round_polynomial.evaluate(challenge);
+
+        self.advance(expected, challenge)
+    }
+
+    /// Checks a compressed round message, the sum over `{0, 1}` holds by construction.
+    pub fn receive_message_with_challenge(
+        &mut self,
+        message: &RoundMessage<F>,
+        challenge: F,
+    ) -> Result<VerifierState<F>, SumcheckError> {
+        if self.accepted || self.randomness.len() >= self.number_of_vars {
+            return Err(SumcheckError::ProtocolFinished);
+        }
+
+        let evaluations = message.decompress(self.expected)?;
+
+        // Degree 0 rounds are sent as degree 1 messages
+        let max_degree = self.max_degree.max(1);
+        if message.degree() > max_degree {
+            return Err(SumcheckError::DegreeTooHigh {
+                round: self.randomness.len(),
+                degree: message.degree(),
+                max_degree,
+            });
+        }
+
+        let expected = self.domain.evaluate(&evaluations, challenge);
+
+        self.advance(expected, challenge)
+    }
+
+    /// Records the challenge of an accepted round, after the last round the
+    /// oracle has to agree with `expected`.
+    fn advance(&mut self, expected: F, challenge: F) -> Result<VerifierState<F>, SumcheckError> {
+        if self.randomness.len() + 1 == self.number_of_vars {
+            let mut point = self.randomness.clone();
+            point.push(challenge);
+
+            if (self.oracle)(&point) != expected {
+                return Err(SumcheckError::FinalEvaluationMismatch);
+            }
+
+            self.accepted = true;
+        }
+
+        self.randomness.push(challenge);
+        self.expected = expected;
+
+        if self.accepted {
+            return Ok(VerifierState::Accepted);
+        }
+
+        Ok(VerifierState::Challenge(challenge))
+    }
+
+    /// Verifies a non-interactive proof and returns the challenges it was bound to,
+    /// `statement` is the one the proof was produced for, see `proof::prove`.
+    pub fn verify_proof(
+        self,
+        proof: &SumcheckProof<F>,
+        statement: &[u8],
+    ) -> Result<Vec<F>, SumcheckError> {
+        let mut transcript =
+            new_transcript(statement, self.number_of_vars, self.max_degree, &self.claim);
+
+        self.verify_proof_with_transcript(proof, &mut transcript)
+    }
+
+    /// Same as `verify_proof` for a proof whose transcript was started by an outer protocol.
+    pub(crate) fn verify_proof_with_transcript(
+        mut self,
+        proof: &SumcheckProof<F>,
+        transcript: &mut Transcript,
+    ) -> Result<Vec<F>, SumcheckError> {
+        if proof.claimed_sum != self.claim {
+            return Err(SumcheckError::ClaimMismatch);
+        }
+
+        self.verify_rounds(&proof.round_messages, transcript)?;
+
+        if self.expected != proof.final_evaluation {
+            return Err(SumcheckError::FinalEvaluationMismatch);
+        }
+
+        Ok(self.randomness)
+    }
+
+    /// Checks every round against a transcript shared with other sub-protocols,
+    /// the counterpart of `prove_rounds`.
+    pub(crate) fn verify_rounds(
+        &mut self,
+        round_messages: &[RoundMessage<F>],
+        transcript: &mut Transcript,
+    ) -> Result<(), SumcheckError> {
+        if round_messages.len() != self.number_of_vars {
+            return Err(SumcheckError::WrongNumberOfRounds {
+                expected: self.number_of_vars,
+                received: round_messages.len(),
+            });
+        }
+
+        for round_message in round_messages {
+            transcript.absorb_round_message(b"round_message", round_message);
+            let challenge = transcript.challenge(b"challenge");
+
+            self.receive_message_with_challenge(round_message, challenge)?;
+        }
+
+        // Without any round `advance` never queries the oracle, the claim is its value
+        if self.number_of_vars == 0 {
+            if (self.oracle)(&[]) != self.expected {
+                return Err(SumcheckError::FinalEvaluationMismatch);
+            }
+
+            self.accepted = true;
+        }
+
+        Ok(())
+    }
+
+    pub fn is_accepted(&self) -> bool {
+        self.accepted
+    }
+
+    pub fn randomness(&self) -> &[F] {
+        &self.randomness
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::SumcheckError;
+    use crate::fp::Fp;
+    use crate::mpolynomial::MPolynomial;
+    use crate::proof::{prove_non_interactive, verify_proof, SumcheckProof};
+    use crate::prover::{Prover, SumcheckProver};
+    use crate::round_message::RoundMessage;
+    use crate::upolynomial::UPolynomial;
+    use crate::verifier::SumcheckVerifier;
+    use crate::VerifierState;
+
+    fn polynomial() -> MPolynomial {
+        MPolynomial::from(vec![Fp(2), Fp(3), Fp(5), Fp(7)], vec![1, 1, 1])
+    }
+
+    #[test]
+    fn test_honest_prover() {
+        let polynomial = polynomial();
+        let mut prover = SumcheckProver::new(polynomial.clone());
+        let mut verifier = SumcheckVerifier::new(Fp(96), 3, 1, |x| polynomial.eval(x));
+
+        let mut challenge = None;
+        loop {
+            match verifier.receive(prover.round(challenge)) {
+                Ok(VerifierState::Challenge(next)) => challenge = Some(next),
+                Ok(VerifierState::Accepted) => break,
+                Err(err) => panic!("honest prover rejected: {}", err),
+            }
+        }
+
+        assert!(verifier.is_accepted());
+        assert_eq!(verifier.randomness().len(), 3);
+    }
+
+    #[test]
+    fn test_adversarial_prover_with_wrong_claim() {
+        let polynomial = polynomial();
+
+        // Claims 97 and sends a line consistent with it in every round, only the
+        // final oracle query reveals the lie
+        let mut verifier = SumcheckVerifier::new(Fp(97), 3, 1, |x| polynomial.eval(x));
+
+        let mut expected = Fp(97);
+        for round in 0..3 {
+            // g(x) = x + (expected - 1) / 2
+            let constant = (expected - Fp(1)) / Fp(2);
+            let malicious = UPolynomial::from_coeffs_be(vec![Fp(1), constant]);
+
+            let result = verifier.receive(Some(malicious));
+
+            if round < 2 {
+                match result {
+                    Ok(VerifierState::Challenge(challenge)) => expected = challenge + constant,
+                    other => panic!("unexpected verifier state {:?}", other),
+                }
+            } else {
+                assert_eq!(result, Err(SumcheckError::FinalEvaluationMismatch));
+            }
+        }
+
+        assert!(!verifier.is_accepted());
+    }
+
+    #[test]
+    fn test_zero_top_coefficients_are_not_counted() {
+        let polynomial = polynomial();
+        let mut prover = SumcheckProver::new(polynomial.clone());
+        let mut verifier = SumcheckVerifier::new(Fp(96), 3, 1, |x| polynomial.eval(x));
+
+        // The honest first round with a zero x^2 coefficient appended
+        let mut padded = prover.round(None).unwrap();
+        padded.coefficients.push(Fp(0));
+
+        assert!(matches!(
+            verifier.receive(Some(padded)),
+            Ok(VerifierState::Challenge(_))
+        ));
+    }
+
+    #[test]
+    fn test_zero_variables() {
+        let polynomial = MPolynomial::constant(0, Fp(5));
+
+        let proof = prove_non_interactive(&polynomial);
+        assert_eq!(verify_proof(&polynomial, &proof, Fp(5)), Ok(vec![]));
+
+        // A prover repeating a wrong claim as the final evaluation
+        let forged = SumcheckProof {
+            claimed_sum: Fp(6),
+            round_messages: vec![],
+            final_evaluation: Fp(6),
+        };
+        assert_eq!(
+            verify_proof(&polynomial, &forged, Fp(6)),
+            Err(SumcheckError::FinalEvaluationMismatch)
+        );
+    }
+
+    #[test]
+    fn test_degree_bound_of_constant_rounds() {
+        let mut verifier = SumcheckVerifier::new(Fp(10), 1, 0, |_| Fp(5));
+        let message = RoundMessage {
+            evaluations: vec![Fp(5), Fp(5), Fp(5)],
+        };
+
+        // The enforced bound is 1, the one a degree 0 round is sent with
+        assert_eq!(
+            verifier.receive_message_with_challenge(&message, Fp(3)),
+            Err(SumcheckError::DegreeTooHigh {
+                round: 0,
+                degree: 3,
+                max_degree: 1
+            })
+        );
+    }
+}
@@ -0,0 +1,163 @@
+use crate::field::Field;
+use crate::fp::Fp;
+use crate::mle::DenseMultilinearExtension;
+use std::rc::Rc;
+
+/// Coefficient and table indices of one product.
+pub type Product<F = Fp> = (F, Vec<usize>);
+
+/// `sum_j c_j * prod_{i in S_j} f_i(x)` over multilinear tables `f_i`.
+///
+/// Products hold indices into one list of distinct tables, a table passed to
+/// several products through the same `Rc` is stored, and later folded, once.
+#[derive(Debug, Clone)]
+pub struct VirtualPolynomial<F: Field = Fp> {
+    number_of_vars: usize,
+    tables: Vec<Rc<DenseMultilinearExtension<F>>>,
+    products: Vec<Product<F>>,
+}
+
+impl<F: Field> VirtualPolynomial<F> {
+    pub fn new(number_of_vars: usize) -> Self {
+        Self {
+            number_of_vars,
+            tables: vec![],
+            products: vec![],
+        }
+    }
+
+    /// Adds `coefficient * prod_i product[i]`.
+    pub fn add_product(&mut self, coefficient: F, product: Vec<Rc<DenseMultilinearExtension<F>>>) {
+        assert!(!product.is_empty(), "product needs at least one table");
+
+        let indices = product
+            .into_iter()
+            .map(|table| {
+                assert_eq!(table.number_of_vars(), self.number_of_vars);
+
+                match self.tables.iter().position(|t| Rc::ptr_eq(t, &table)) {
+                    Some(index) => index,
+                    None => {
+                        self.tables.push(table);
+                        self.tables.len() - 1
+                    }
+                }
+            })
+            .collect();
+
+        self.products.push((coefficient, indices));
+    }
+
+    pub fn number_of_vars(&self) -> usize {
+        self.number_of_vars
+    }
+
+    /// Number of distinct tables.
+    pub fn number_of_tables(&self) -> usize {
+        self.tables.len()
+    }
+
+    /// Coefficients and table indices of every product.
+    pub fn products(&self) -> &[Product<F>] {
+        &self.products
+    }
+
+    /// Degree in each variable, the size of the largest product.
+    pub fn degree(&self) -> usize {
+        self.products
+            .iter()
+            .map(|(_, indices)| indices.len())
+            .max()
+            .unwrap_or(0)
+    }
+
+    pub fn eval(&self, x: &[F]) -> F {
+        let values: Vec<F> = self.tables.iter().map(|table| table.eval(x)).collect();
+
+        combine(&self.products, &values)
+    }
+
+    pub fn sum_over_hyper_cube(&self) -> F {
+        (0..1 << self.number_of_vars).fold(F::zero(), |acc, i| {
+            let values: Vec<F> = self
+                .tables
+                .iter()
+                .map(|table| table.evaluations[i])
+                .collect();
+
+            acc + combine(&self.products, &values)
+        })
+    }
+
+    /// Takes the tables out, cloning only those still shared outside of this polynomial.
+    pub(crate) fn into_parts(self) -> (Vec<DenseMultilinearExtension<F>>, Vec<Product<F>>) {
+        let tables = self
+            .tables
+            .into_iter()
+            .map(|table| Rc::try_unwrap(table).unwrap_or_else(|shared| (*shared).clone()))
+            .collect();
+
+        (tables, self.products)
+    }
+}
+
+/// `sum_j c_j * prod_{i in S_j} values[i]`
+pub(crate) fn combine<F: Field>(products: &[Product<F>], values: &[F]) -> F {
+    products
+        .iter()
+        .fold(F::zero(), |acc, (coefficient, indices)| {
+            acc + indices
+                .iter()
+                .fold(*coefficient, |product, i| product * values[*i])
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fp::Fp;
+    use crate::mle::DenseMultilinearExtension;
+    use crate::virtual_polynomial::VirtualPolynomial;
+    use std::rc::Rc;
+
+    fn table(seed: u64) -> Rc<DenseMultilinearExtension> {
+        Rc::new(DenseMultilinearExtension::from(
+            (0..8).map(|i| Fp(i * seed + 1)).collect(),
+        ))
+    }
+
+    #[test]
+    fn test_shared_tables_are_stored_once() {
+        let (f1, f2, f3) = (table(2), table(3), table(5));
+
+        let mut polynomial = VirtualPolynomial::new(3);
+        polynomial.add_product(Fp(1), vec![f1.clone(), f2.clone()]);
+        polynomial.add_product(Fp(3), vec![f2.clone(), f3.clone()]);
+        polynomial.add_product(Fp(5), vec![f1.clone()]);
+
+        assert_eq!(polynomial.number_of_tables(), 3);
+        assert_eq!(polynomial.degree(), 2);
+        assert_eq!(polynomial.products()[1], (Fp(3), vec![1, 2]));
+
+        let x = [Fp(7), Fp(11), Fp(13)];
+        assert_eq!(
+            polynomial.eval(&x),
+            f1.eval(&x) * f2.eval(&x) + Fp(3) * f2.eval(&x) * f3.eval(&x) + Fp(5) * f1.eval(&x)
+        );
+    }
+
+    #[test]
+    fn test_sum_over_hyper_cube() {
+        let (f1, f2) = (table(2), table(3));
+
+        let mut polynomial = VirtualPolynomial::new(3);
+        polynomial.add_product(Fp(2), vec![f1.clone(), f2.clone(), f2.clone()]);
+
+        let expected = (0..8).fold(Fp(0), |acc, i| {
+            acc + Fp(2) * f1.evaluations[i] * f2.evaluations[i] * f2.evaluations[i]
+        });
+
+        assert_eq!(polynomial.number_of_tables(), 2);
+        assert_eq!(polynomial.degree(), 3);
+        assert_eq!(polynomial.sum_over_hyper_cube(), expected);
+    }
+}
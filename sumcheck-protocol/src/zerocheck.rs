@@ -0,0 +1,367 @@
+use crate::error::SumcheckError;
+use crate::field::Field;
+use crate::fp::Fp;
+use crate::mle::{eq_eval, eq_table};
+use crate::mpolynomial::MPolynomial;
+use crate::proof::{prove_rounds, SumcheckProof};
+use crate::prover::Prover;
+use crate::transcript::Transcript;
+use crate::upolynomial::UPolynomial;
+use crate::verifier::SumcheckVerifier;
+use crate::{SumcheckProtocol, VerifierState};
+
+/// `eq(r, x) = prod_j (r_j * x_j + (1 - r_j) * (1 - x_j))` as a polynomial in `x`.
+///
+/// Prefer `mle::eq_eval` for a single evaluation and `mle::eq_table` for all of the hypercube,
+/// the expanded polynomial has `2^n` terms.
+pub fn eq_polynomial<F: Field>(r: &[F]) -> MPolynomial<F> {
+    let number_of_vars = r.len();
+
+    r.iter().enumerate().fold(
+        MPolynomial::constant(number_of_vars, F::one()),
+        |acc, (j, r_j)| {
+            let mut exponents = vec![0; number_of_vars];
+            exponents[j] = 1;
+
+            let factor = MPolynomial::from_terms(
+                number_of_vars,
+                vec![
+                    (F::one() - *r_j, vec![0; number_of_vars]),
+                    (*r_j + *r_j - F::one(), exponents),
+                ],
+            );
+
+            acc * factor
+        },
+    )
+}
+
+/// Prover for `sum_x eq(r, x) * P(x)` that keeps `eq(r, x)` as a factor.
+///
+/// `eq` splits into one factor per variable: after fixing `x_1..x_j` to the challenges,
+/// the round polynomial is `eq(r_{<=j}, challenges) * eq(r_{j+1}, t)` times the sum of
+/// `P` against `eq_table` of the remaining coordinates of `r`. The `2^n` terms of the
+/// expanded `eq_polynomial(r) * P` are never built.
+pub struct ZeroCheckProver<F: Field = Fp> {
+    polynomial: MPolynomial<F>,
+    r: Vec<F>,
+    randomness: Vec<F>,
+    /// `eq` of the fixed coordinates of `r` and the challenges.
+    scale: F,
+}
+
+impl<F: Field> ZeroCheckProver<F> {
+    pub fn new(polynomial: MPolynomial<F>, r: Vec<F>) -> Self {
+        assert_eq!(r.len(), polynomial.number_of_vars());
+
+        Self {
+            polynomial,
+            r,
+            randomness: vec![],
+            scale: F::one(),
+        }
+    }
+}
+
+/// `sum_i weights[i] * polynomial(x_i)` over the hypercube, in `eq_table` order.
+fn weighted_sum<F: Field>(polynomial: &MPolynomial<F>, weights: &[F]) -> F {
+    let number_of_vars = polynomial.number_of_vars();
+
+    weights
+        .iter()
+        .enumerate()
+        .fold(F::zero(), |acc, (i, weight)| {
+            let x: Vec<F> = (0..number_of_vars)
+                .map(|j| F::from_u64((i as u64 >> j) & 1))
+                .collect();
+
+            acc + *weight * polynomial.eval(&x)
+        })
+}
+
+impl<F: Field> Prover<F> for ZeroCheckProver<F> {
+    fn claimed_sum(&self) -> F {
+        weighted_sum(&self.polynomial, &eq_table(&self.r))
+    }
+
+    fn number_of_vars(&self) -> usize {
+        self.polynomial.number_of_vars()
+    }
+
+    /// `eq(r, x)` adds one to the degree of every variable.
+    fn degree(&self) -> usize {
+        self.polynomial.degree_ind() + 1
+    }
+
+    fn round_evaluations(&mut self, challenge: Option<F>) -> Option<Vec<F>> {
+        if let Some(challenge) = challenge {
+            let j = self.randomness.len();
+
+            self.scale = self.scale * eq_eval(&[self.r[j]], &[challenge]);
+            self.randomness.push(challenge);
+        }
+
+        let j = self.randomness.len();
+        if j >= self.polynomial.number_of_vars() {
+            return None;
+        }
+
+        let remaining = self.polynomial.partial_eval(&self.randomness);
+        let weights = eq_table(&self.r[j + 1..]);
+
+        let evaluations = (0..=remaining.degree(0) as u64 + 1)
+            .map(|t| {
+                let t = F::from_u64(t);
+                let factor = self.scale * eq_eval(&[self.r[j]], &[t]);
+
+                factor * weighted_sum(&remaining.partial_eval(&[t]), &weights)
+            })
+            .collect();
+
+        Some(evaluations)
+    }
+
+    fn final_evaluation(&self) -> F {
+        self.scale * self.polynomial.eval(&self.randomness)
+    }
+}
+
+/// Proves that a polynomial vanishes on all of `{0, 1}^n`.
+///
+/// `sum_x eq(r, x) * P(x)` is the multilinear extension of `P` restricted to the hypercube,
+/// evaluated at `r`. It is the zero polynomial exactly when `P` vanishes on the hypercube,
+/// so for a random `r` the sumcheck is run with claim zero.
+pub struct ZeroCheck<F: Field = Fp> {
+    protocol: SumcheckProtocol<F, ZeroCheckProver<F>>,
+}
+
+impl<F: Field> ZeroCheck<F> {
+    /// The verifier samples `r` itself.
+    pub fn new(polynomial: MPolynomial<F>) -> Self {
+        let r = (0..polynomial.number_of_vars())
+            .map(|_| F::sample())
+            .collect();
+
+        Self::from(polynomial, r)
+    }
+
+    pub fn from(polynomial: MPolynomial<F>, r: Vec<F>) -> Self {
+        let prover = ZeroCheckProver::new(polynomial.clone(), r.clone());
+        let protocol = SumcheckProtocol::from_prover(prover, F::zero(), move |x| {
+            eq_eval(&r, x) * polynomial.eval(x)
+        });
+
+        Self { protocol }
+    }
+
+    pub fn prove(&mut self) -> Option<UPolynomial<F>> {
+        self.protocol.prove()
+    }
+
+    pub fn verify(
+        &mut self,
+        rec_polynomial: Option<UPolynomial<F>>,
+    ) -> Result<VerifierState<F>, SumcheckError> {
+        self.protocol.verify(rec_polynomial)
+    }
+
+    pub fn is_verifier_accept(&self) -> bool {
+        self.protocol.is_verifier_accept()
+    }
+}
+
+/// Binds the polynomial before `r` is drawn, a prover knowing `r` in advance could
+/// build a non-vanishing polynomial whose `sum_x eq(r, x) * P(x)` is zero.
+fn new_transcript<F: Field>(polynomial: &MPolynomial<F>) -> (Transcript, Vec<F>) {
+    let mut transcript = Transcript::new(b"zerocheck");
+
+    transcript.absorb_bytes(b"polynomial", &polynomial.to_bytes());
+    transcript.absorb_u64(b"number_of_vars", polynomial.number_of_vars() as u64);
+    transcript.absorb_u64(b"degree", polynomial.degree_ind() as u64);
+
+    let r = (0..polynomial.number_of_vars())
+        .map(|_| transcript.challenge(b"r"))
+        .collect();
+
+    (transcript, r)
+}
+
+/// Non-interactive zerocheck, `r` and the round challenges come from one transcript.
+pub fn prove_zerocheck<F: Field>(polynomial: &MPolynomial<F>) -> SumcheckProof<F> {
+    let (mut transcript, r) = new_transcript(polynomial);
+
+    let mut prover = ZeroCheckProver::new(polynomial.clone(), r);
+    let (round_messages, _) = prove_rounds(&mut prover, &mut transcript);
+
+    SumcheckProof {
+        claimed_sum: F::zero(),
+        round_messages,
+        final_evaluation: prover.final_evaluation(),
+    }
+}
+
+/// Checks a proof from `prove_zerocheck` and returns the point it reduced to.
+pub fn verify_zerocheck<F: Field>(
+    polynomial: &MPolynomial<F>,
+    proof: &SumcheckProof<F>,
+) -> Result<Vec<F>, SumcheckError> {
+    let (mut transcript, r) = new_transcript(polynomial);
+
+    // eq(r, x) adds one to the degree of every variable
+    SumcheckVerifier::new(
+        F::zero(),
+        polynomial.number_of_vars(),
+        polynomial.degree_ind() + 1,
+        |x| eq_eval(&r, x) * polynomial.eval(x),
+    )
+    .verify_proof_with_transcript(proof, &mut transcript)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::SumcheckError;
+    use crate::fp::Fp;
+    use crate::mle::eq_eval;
+    use crate::mpolynomial::MPolynomial;
+    use crate::prover::{Prover, SumcheckProver};
+    use crate::transcript::Transcript;
+    use crate::zerocheck::{
+        eq_polynomial, prove_zerocheck, verify_zerocheck, ZeroCheck, ZeroCheckProver,
+    };
+    use crate::VerifierState;
+
+    /// `x1^2 - x1 + 3 * x3 * (x2^2 - x2)`, zero on the hypercube but not everywhere
+    fn vanishing() -> MPolynomial {
+        MPolynomial::from_terms(
+            3,
+            vec![
+                (Fp(1), vec![2, 0, 0]),
+                (-Fp(1), vec![1, 0, 0]),
+                (Fp(3), vec![0, 2, 1]),
+                (-Fp(3), vec![0, 1, 1]),
+            ],
+        )
+    }
+
+    /// `x1 - x2` sums to zero over the hypercube without vanishing on it
+    fn non_vanishing() -> MPolynomial {
+        MPolynomial::from_terms(2, vec![(Fp(1), vec![1, 0]), (-Fp(1), vec![0, 1])])
+    }
+
+    fn run(zerocheck: &mut ZeroCheck) -> Result<VerifierState, SumcheckError> {
+        loop {
+            let step = zerocheck.prove();
+
+            match zerocheck.verify(step)? {
+                VerifierState::Accepted => return Ok(VerifierState::Accepted),
+                VerifierState::Challenge(_) => continue,
+            }
+        }
+    }
+
+    #[test]
+    fn test_eq_polynomial() {
+        let r = [Fp(3), Fp(5), Fp(7)];
+        let eq = eq_polynomial(&r);
+
+        assert_eq!(eq.degree_ind(), 1);
+        assert_eq!(eq.eval(&r[..]), eq_eval(&r, &r));
+
+        let x = [Fp::sample(), Fp::sample(), Fp::sample()];
+        assert_eq!(eq.eval(&x), eq_eval(&r, &x));
+        assert_eq!(
+            eq.eval(&[Fp(0), Fp(1), Fp(1)]),
+            eq_eval(&r, &[Fp(0), Fp(1), Fp(1)])
+        );
+    }
+
+    #[test]
+    fn test_vanishing_polynomial_is_accepted() {
+        let polynomial = vanishing();
+        assert_ne!(polynomial.eval(&[Fp(2), Fp(0), Fp(0)]), Fp(0));
+
+        let mut zerocheck = ZeroCheck::new(polynomial);
+
+        assert_eq!(run(&mut zerocheck), Ok(VerifierState::Accepted));
+        assert!(zerocheck.is_verifier_accept());
+    }
+
+    #[test]
+    fn test_non_vanishing_polynomial_is_rejected() {
+        let polynomial = non_vanishing();
+        assert_eq!(polynomial.sum_over_hyper_cube(None), Fp(0));
+
+        let mut zerocheck = ZeroCheck::from(polynomial, vec![Fp(3), Fp(5)]);
+
+        assert_eq!(
+            run(&mut zerocheck),
+            Err(SumcheckError::RoundSumMismatch { round: 0 })
+        );
+    }
+
+    #[test]
+    fn test_non_interactive() {
+        let polynomial = vanishing();
+        let proof = prove_zerocheck(&polynomial);
+
+        assert_eq!(proof.round_messages.len(), 3);
+        assert!(verify_zerocheck(&polynomial, &proof).is_ok());
+
+        let proof = prove_zerocheck(&non_vanishing());
+        assert!(verify_zerocheck(&non_vanishing(), &proof).is_err());
+    }
+
+    #[test]
+    fn test_r_is_bound_to_the_polynomial() {
+        // r drawn from the number of variables alone is known before P is chosen
+        let mut transcript = Transcript::new(b"zerocheck");
+        transcript.absorb_u64(b"number_of_vars", 1);
+        let r: Fp = transcript.challenge(b"r");
+
+        // r - x is non-zero on {0, 1}, but eq(r, x) * (r - x) sums to zero
+        let forged = MPolynomial::from_terms(1, vec![(r, vec![0]), (-Fp(1), vec![1])]);
+        assert_eq!(
+            (eq_polynomial(&[r]) * forged.clone()).sum_over_hyper_cube(None),
+            Fp(0)
+        );
+
+        let proof = prove_zerocheck(&forged);
+        assert!(verify_zerocheck(&forged, &proof).is_err());
+    }
+
+    #[test]
+    fn test_prover_matches_expanded_product() {
+        let polynomial = vanishing();
+        let r = vec![Fp(3), Fp(5), Fp(7)];
+
+        let mut factored = ZeroCheckProver::new(polynomial.clone(), r.clone());
+        let mut expanded = SumcheckProver::new(eq_polynomial(&r) * polynomial);
+
+        assert_eq!(factored.claimed_sum(), expanded.claimed_sum());
+        assert_eq!(factored.degree(), expanded.degree());
+
+        let mut challenge = None;
+        for next in [Fp(11), Fp(13), Fp(17), Fp(19)] {
+            assert_eq!(
+                factored.round_evaluations(challenge),
+                expanded.round_evaluations(challenge)
+            );
+            challenge = Some(next);
+        }
+
+        assert_eq!(factored.final_evaluation(), expanded.final_evaluation());
+    }
+
+    #[test]
+    fn test_reject_tampered_final_evaluation() {
+        let polynomial = vanishing();
+        let mut proof = prove_zerocheck(&polynomial);
+
+        proof.final_evaluation = proof.final_evaluation + Fp(1);
+
+        assert_eq!(
+            verify_zerocheck(&polynomial, &proof),
+            Err(SumcheckError::FinalEvaluationMismatch)
+        );
+    }
+}